@@ -0,0 +1,195 @@
+use std::{collections::BTreeMap, path::Path};
+
+use crate::{
+    common::{error::AocError, parsing::Span, table::render},
+    registry::{Part, Solution},
+    runner::{Outcome, RunResult, run_parallel},
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputFile {
+    pub day: u32,
+    pub name: String,
+    pub path: String,
+}
+
+// every `<root>/day_XX/<name>.txt`, ordered by day and then name
+pub fn discover(root: &str) -> Result<Vec<InputFile>, AocError> {
+    let mut inputs = vec![];
+    for entry in std::fs::read_dir(root).map_err(|_| AocError::FileNotFound)?.flatten() {
+        let dir_name = entry.file_name().to_string_lossy().to_string();
+        let Some(day) = dir_name.strip_prefix("day_").and_then(|day| day.parse::<u32>().ok()) else {
+            continue;
+        };
+        for file in std::fs::read_dir(entry.path()).map_err(|_| AocError::FileNotFound)?.flatten() {
+            let path = file.path();
+            if path.extension().is_some_and(|extension| extension == "txt") {
+                let name = path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+                inputs.push(InputFile { day, name, path: path.to_string_lossy().to_string() });
+            }
+        }
+    }
+    inputs.sort_by(|a, b| (a.day, &a.name).cmp(&(b.day, &b.name)));
+    Ok(inputs)
+}
+
+type AnswerKey = (u32, Part, String);
+
+// <day> <part> <input name> <answer>, blank lines and `#` comments are skipped
+pub fn parse_answers(s: &str) -> Result<BTreeMap<AnswerKey, String>, AocError> {
+    let mut answers = BTreeMap::new();
+    for line in Span::document(s).lines() {
+        if line.is_blank() || line.text.starts_with('#') {
+            continue;
+        }
+        let words: Vec<Span> = line.words().collect();
+        let [day, part, name, answer] = words.as_slice() else {
+            return Err(line.error("expected `<day> <part> <input name> <answer>`"));
+        };
+        let part = part.text.parse::<Part>().map_err(|reason| part.error(reason))?;
+        answers.insert((day.parse("day")?, part, name.text.to_string()), answer.text.to_string());
+    }
+    Ok(answers)
+}
+
+pub fn load_answers(filename: &str) -> Result<BTreeMap<AnswerKey, String>, AocError> {
+    if !Path::new(filename).exists() {
+        return Ok(BTreeMap::new());
+    }
+    let s = std::fs::read_to_string(filename).map_err(|_| AocError::FileNotFound)?;
+    parse_answers(&s)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Verdict {
+    Correct,
+    Wrong,
+    Unknown,
+}
+
+#[derive(Debug)]
+pub struct BatchResult {
+    pub input: InputFile,
+    pub result: RunResult,
+    pub expected: Option<String>,
+}
+
+impl BatchResult {
+    pub fn verdict(&self) -> Verdict {
+        match (&self.result.outcome, &self.expected) {
+            (_, None) => Verdict::Unknown,
+            (Outcome::Answer(answer), Some(expected)) if answer == expected => Verdict::Correct,
+            (_, Some(_)) => Verdict::Wrong,
+        }
+    }
+}
+
+pub fn run_batch(
+    inputs: &[InputFile],
+    solutions: &[Solution],
+    answers: &BTreeMap<AnswerKey, String>,
+    threads: Option<usize>,
+) -> Result<Vec<BatchResult>, AocError> {
+    let pairs: Vec<(&InputFile, Solution)> = inputs.iter()
+        .flat_map(|input| solutions.iter().filter(|s| s.day == input.day).map(move |s| (input, *s)))
+        .collect();
+    let jobs = pairs.iter().map(|(input, solution)| (*solution, input.path.clone())).collect();
+    let results = run_parallel(jobs, threads)?;
+    Ok(pairs.into_iter().zip(results)
+        .map(|((input, solution), result)| BatchResult {
+            input: input.clone(),
+            expected: answers.get(&(solution.day, solution.part, input.name.clone())).cloned(),
+            result,
+        })
+        .collect())
+}
+
+fn cell(result: &BatchResult) -> String {
+    let text = match &result.result.outcome {
+        Outcome::Answer(answer) => answer.clone(),
+        Outcome::Failed(_) => "error".to_string(),
+        Outcome::Panicked(_) => "panic".to_string(),
+    };
+    match result.verdict() {
+        Verdict::Correct => format!("{text} ok"),
+        Verdict::Wrong => format!("{text} WRONG"),
+        Verdict::Unknown => text,
+    }
+}
+
+// expected answer and the cell for each variant
+type MatrixRow<'a> = (Option<&'a str>, BTreeMap<&'a str, String>);
+
+// one row per day, part and input, one column per variant
+pub fn format_matrix(results: &[BatchResult]) -> String {
+    let mut variants: Vec<&str> = vec![];
+    let mut rows: BTreeMap<(u32, Part, &str), MatrixRow> = BTreeMap::new();
+    for result in results {
+        let solution = result.result.solution;
+        if !variants.contains(&solution.variant) {
+            variants.push(solution.variant);
+        }
+        let row = rows.entry((solution.day, solution.part, result.input.name.as_str()))
+            .or_insert((result.expected.as_deref(), BTreeMap::new()));
+        row.1.insert(solution.variant, cell(result));
+    }
+
+    let mut header = vec!["day".to_string(), "part".to_string(), "input".to_string(), "expected".to_string()];
+    header.extend(variants.iter().map(|variant| variant.to_string()));
+    let mut table: Vec<Vec<String>> = vec![header];
+    for ((day, part, name), (expected, cells)) in &rows {
+        let mut row = vec![day.to_string(), part.to_string(), name.to_string(), expected.unwrap_or("?").to_string()];
+        row.extend(variants.iter().map(|variant| cells.get(variant).cloned().unwrap_or_default()));
+        table.push(row);
+    }
+    let mut out = render(&table);
+    let wrong = results.iter().filter(|result| result.verdict() == Verdict::Wrong).count();
+    let checked = results.iter().filter(|result| result.verdict() != Verdict::Unknown).count();
+    out.push_str(&format!("{} runs, {} checked against expected answers, {} wrong\n", results.len(), checked, wrong));
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use crate::registry::{IDIOMATIC, select};
+
+    use super::*;
+
+    #[test]
+    fn test_discover() {
+        let inputs = discover("input").unwrap();
+        let day_01: Vec<&str> = inputs.iter()
+            .filter(|input| input.day == 1)
+            .map(|input| input.name.as_str())
+            .collect();
+        assert_eq!(vec!["easy_test", "hard_test", "missing_digits_test", "puzzle"], day_01);
+        assert!(inputs.iter().any(|input| input.path == "input/day_06/puzzle.txt"));
+    }
+
+    #[test]
+    fn test_parse_answers() {
+        let answers = parse_answers("# comment\n\n1 easy puzzle 55108\n").unwrap();
+        assert_eq!(Some(&"55108".to_string()), answers.get(&(1, Part::Easy, "puzzle".to_string())));
+        assert_eq!(Err(AocError::at(1, 3, "expected `easy` or `hard`, found `medium`")), parse_answers("1 medium x 2"));
+        assert_eq!(Err(AocError::at(2, 1, "expected `<day> <part> <input name> <answer>`")), parse_answers("\n1 easy"));
+    }
+
+    #[test]
+    fn test_batch() {
+        let inputs: Vec<InputFile> = discover("input").unwrap().into_iter()
+            .filter(|input| input.day == 1)
+            .collect();
+        let answers = load_answers("input/answers.txt").unwrap();
+        let mut solutions = select(IDIOMATIC);
+        solutions.extend(select("bytes"));
+        let results = run_batch(&inputs, &solutions, &answers, Some(2)).unwrap();
+        // 4 inputs, 2 parts, 2 variants
+        assert_eq!(16, results.len());
+        assert!(results.iter().all(|result| result.verdict() != Verdict::Wrong));
+
+        let matrix = format_matrix(&results);
+        assert!(matrix.contains("1    easy  puzzle               55108     55108 ok   55108 ok"), "{matrix}");
+        assert!(matrix.contains("1    hard  missing_digits_test  ?         error      error"), "{matrix}");
+        assert!(matrix.ends_with("16 runs, 8 checked against expected answers, 0 wrong\n"), "{matrix}");
+    }
+}
@@ -0,0 +1,83 @@
+use clap::{Parser, Subcommand};
+
+use crate::{registry::{IDIOMATIC, Part}, remote::http::{BASE_URL, YEAR}};
+
+#[derive(Debug, Parser)]
+#[command(about = "Advent of Code 2023 solutions")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    #[command(about = "Solve every day in order with one variant, stopping at the first failure")]
+    Run {
+        #[arg(long, default_value = IDIOMATIC)]
+        variant: String,
+    },
+    #[command(about = "Solve every registered solution in parallel and print a summary table")]
+    All {
+        #[arg(long, help = "only run this variant")]
+        variant: Option<String>,
+        #[arg(long, help = "worker threads, defaults to one per core")]
+        threads: Option<usize>,
+    },
+    #[command(about = "Solve every input under a directory with every variant and compare against expected answers")]
+    Batch {
+        #[arg(long, default_value = "input", help = "directory holding day_XX/<name>.txt inputs")]
+        dir: String,
+        #[arg(long, default_value = "input/answers.txt", help = "lines of `<day> <part> <input name> <answer>`")]
+        answers: String,
+        #[arg(long, help = "only run this variant")]
+        variant: Option<String>,
+        #[arg(long, help = "worker threads, defaults to one per core")]
+        threads: Option<usize>,
+    },
+    #[command(about = "Download missing puzzle inputs into the input directory")]
+    Fetch {
+        #[arg(long, help = "only fetch this day, defaults to every registered day")]
+        day: Option<u32>,
+        #[arg(long, default_value = "input")]
+        dir: String,
+        #[arg(long, default_value = BASE_URL)]
+        base_url: String,
+        #[arg(long, default_value_t = YEAR)]
+        year: u32,
+        #[arg(long, default_value = ".session", help = "file holding the session cookie when AOC_SESSION is not set")]
+        session_file: String,
+    },
+    #[command(about = "Submit an answer, solving the puzzle input when no answer is given")]
+    Submit {
+        day: u32,
+        #[arg(help = "easy or hard")]
+        part: Part,
+        answer: Option<String>,
+        #[arg(long, default_value = "input/attempts.txt", help = "where every submission is recorded")]
+        log: String,
+        #[arg(long, default_value = BASE_URL)]
+        base_url: String,
+        #[arg(long, default_value_t = YEAR)]
+        year: u32,
+        #[arg(long, default_value = ".session", help = "file holding the session cookie when AOC_SESSION is not set")]
+        session_file: String,
+    },
+    #[command(about = "Generate the modules, registry entries and example input of a new day")]
+    NewDay {
+        day: u32,
+    },
+    #[command(about = "Re-solve a day's example inputs whenever they change, source edits need a rebuild and restart")]
+    Watch {
+        day: u32,
+        #[arg(long, default_value = IDIOMATIC)]
+        variant: String,
+        #[arg(long, default_value = "input")]
+        dir: String,
+        #[arg(long, default_value = "input/answers.txt", help = "lines of `<day> <part> <input name> <answer>`")]
+        answers: String,
+        #[arg(long, help = "also watch the puzzle input")]
+        puzzle: bool,
+        #[arg(long, default_value_t = 500, help = "milliseconds between checks")]
+        interval: u64,
+    },
+}
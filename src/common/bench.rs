@@ -0,0 +1,10 @@
+use std::time::{Duration, Instant};
+
+
+pub fn measure<T>(label: &str, f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = std::hint::black_box(f());
+    let elapsed = start.elapsed();
+    println!("{label}: {elapsed:?}");
+    (result, elapsed)
+}
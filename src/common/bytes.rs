@@ -0,0 +1,54 @@
+use super::error::AocError;
+
+// helpers for the zero-copy solvers, which work on the raw bytes of an input
+// and never allocate per line
+
+// lines without their terminator, a trailing newline does not start an
+// empty last line
+pub fn lines(input: &[u8]) -> impl Iterator<Item = &[u8]> {
+    let input = input.strip_suffix(b"\n").unwrap_or(input);
+    input.split(|b| *b == b'\n')
+        .filter(move |_| !input.is_empty())
+        .map(|line| line.strip_suffix(b"\r").unwrap_or(line))
+}
+
+pub fn parse_u64(digits: &[u8]) -> Result<u64, AocError> {
+    if digits.is_empty() {
+        return Err(AocError::ParseError);
+    }
+    digits.iter().try_fold(0u64, |value, b| {
+        if !b.is_ascii_digit() {
+            return Err(AocError::ParseError);
+        }
+        value.checked_mul(10)
+            .and_then(|value| value.checked_add((b - b'0') as u64))
+            .ok_or(AocError::Overflow)
+    })
+}
+
+// every maximal run of ASCII digits, anything else separates numbers
+pub fn numbers(input: &[u8]) -> impl Iterator<Item = Result<u64, AocError>> + '_ {
+    input.split(|b| !b.is_ascii_digit())
+        .filter(|digits| !digits.is_empty())
+        .map(parse_u64)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_lines() {
+        let lines: Vec<&[u8]> = lines(b"a\r\nb\n\nc\n").collect();
+        assert_eq!(vec![&b"a"[..], b"b", b"", b"c"], lines);
+        assert_eq!(0, super::lines(b"").count());
+    }
+
+    #[test]
+    fn test_numbers() {
+        let numbers: Result<Vec<u64>, AocError> = numbers(b"Game 12: 3 blue, 40 red").collect();
+        assert_eq!(Ok(vec![12, 3, 40]), numbers);
+        assert_eq!(Err(AocError::Overflow), parse_u64(b"18446744073709551616"));
+        assert_eq!(Err(AocError::ParseError), parse_u64(b"1x"));
+    }
+}
@@ -5,5 +5,17 @@ pub enum AocError {
     FileNotFound,
     ParseError,
     SolveError,
+    Overflow,
+    UnknownColor { color: String, context: String },
+    Located { line: usize, column: usize, reason: String },
+    // status is missing when the server could not be reached at all
+    Remote { status: Option<u16>, reason: String },
+    Scaffold { reason: String },
+}
+
+impl AocError {
+    pub fn at(line: usize, column: usize, reason: impl Into<String>) -> Self {
+        AocError::Located { line, column, reason: reason.into() }
+    }
 }
 
@@ -10,6 +10,10 @@ pub fn file_to_lines(filename: &str) -> Result<std::io::Lines<BufReader<File>>,
     Ok(reader.lines())
 }
 
-pub fn to_parse_error<T, E>(res: Result<T, E>) -> Result<T, AocError> {
-    res.map_err(|_| AocError::ParseError)
-}
\ No newline at end of file
+// the file was opened, so a line that cannot be read is reported where it is
+pub fn unreadable_line(line: usize, error: std::io::Error) -> AocError {
+    match error.kind() {
+        std::io::ErrorKind::InvalidData => AocError::at(line, 1, "line is not valid UTF-8"),
+        _ => AocError::at(line, 1, format!("could not read line: {error}")),
+    }
+}
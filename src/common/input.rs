@@ -0,0 +1,56 @@
+use std::{fs::File, ops::Deref};
+
+use memmap2::Mmap;
+
+use super::error::AocError;
+
+#[allow(unused)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Loader {
+    Read,
+    // the file must not be modified while it is mapped
+    Mmap,
+}
+
+pub enum Input {
+    Owned(Vec<u8>),
+    Mapped(Mmap),
+}
+
+impl Deref for Input {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            Input::Owned(bytes) => bytes,
+            Input::Mapped(map) => map,
+        }
+    }
+}
+
+pub fn load(filename: &str, loader: Loader) -> Result<Input, AocError> {
+    match loader {
+        Loader::Read => std::fs::read(filename)
+            .map(Input::Owned)
+            .map_err(|_| AocError::FileNotFound),
+        Loader::Mmap => {
+            let file = File::open(filename).map_err(|_| AocError::FileNotFound)?;
+            // SAFETY: inputs are only read, and are not expected to change while a day is solved
+            let map = unsafe { Mmap::map(&file) }.map_err(|_| AocError::FileNotFound)?;
+            Ok(Input::Mapped(map))
+        },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_loaders_agree() {
+        let read = load("input/day_06/easy_test.txt", Loader::Read).unwrap();
+        let mapped = load("input/day_06/easy_test.txt", Loader::Mmap).unwrap();
+        assert_eq!(&*read, &*mapped);
+        assert!(load("input/day_06/missing.txt", Loader::Mmap).is_err());
+    }
+}
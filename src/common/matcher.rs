@@ -0,0 +1,152 @@
+use std::ops::Range;
+
+// a byte-level trie over a fixed set of patterns, matching is done by walking
+// the trie from a candidate start position, so overlapping patterns such as
+// "eightwo" are found at both offsets and no allocation happens per lookup
+
+#[derive(Debug)]
+struct Node<T> {
+    children: Vec<(u8, usize)>,
+    value: Option<T>,
+}
+
+impl<T> Default for Node<T> {
+    fn default() -> Self {
+        Node { children: vec![], value: None }
+    }
+}
+
+#[derive(Debug)]
+pub struct PatternMatcher<T> {
+    nodes: Vec<Node<T>>,
+    // only ASCII letters are folded, other bytes must match exactly
+    ignore_ascii_case: bool,
+}
+
+impl<T: Copy> PatternMatcher<T> {
+    #[allow(unused)]
+    pub fn new<'a>(patterns: impl IntoIterator<Item = (&'a str, T)>) -> Self {
+        Self::with_case(patterns, false)
+    }
+
+    pub fn with_case<'a>(patterns: impl IntoIterator<Item = (&'a str, T)>, ignore_ascii_case: bool) -> Self {
+        let mut matcher = PatternMatcher { nodes: vec![Node::default()], ignore_ascii_case };
+        for (pattern, value) in patterns {
+            matcher.insert(pattern.as_bytes(), value);
+        }
+        matcher
+    }
+
+    fn fold(&self, byte: u8) -> u8 {
+        if self.ignore_ascii_case {
+            byte.to_ascii_lowercase()
+        } else {
+            byte
+        }
+    }
+
+    fn child(&self, node: usize, byte: u8) -> Option<usize> {
+        let byte = self.fold(byte);
+        self.nodes[node].children.iter()
+            .find(|(b, _)| *b == byte)
+            .map(|(_, child)| *child)
+    }
+
+    pub fn insert(&mut self, pattern: &[u8], value: T) {
+        let mut node = 0;
+        for byte in pattern {
+            node = match self.child(node, *byte) {
+                Some(child) => child,
+                None => {
+                    self.nodes.push(Node::default());
+                    let child = self.nodes.len() - 1;
+                    let byte = self.fold(*byte);
+                    self.nodes[node].children.push((byte, child));
+                    child
+                },
+            };
+        }
+        // the first value registered for a pattern wins
+        self.nodes[node].value.get_or_insert(value);
+    }
+
+    // the longest pattern starting exactly at the start of `haystack`, with its length
+    pub fn match_at(&self, haystack: &[u8]) -> Option<(T, usize)> {
+        let mut node = 0;
+        let mut longest = None;
+        for (i, byte) in haystack.iter().enumerate() {
+            match self.child(node, *byte) {
+                Some(child) => node = child,
+                None => break,
+            }
+            if let Some(value) = self.nodes[node].value {
+                longest = Some((value, i + 1));
+            }
+        }
+        longest
+    }
+
+    fn match_range(&self, haystack: &[u8], start: usize) -> Option<(T, Range<usize>)> {
+        self.match_at(&haystack[start..])
+            .map(|(value, len)| (value, start..start + len))
+    }
+
+    // the match with the smallest start offset, and where it was found
+    pub fn first_match(&self, haystack: &[u8]) -> Option<(T, Range<usize>)> {
+        (0..haystack.len())
+            .find_map(|start| self.match_range(haystack, start))
+    }
+
+    // the match with the largest start offset, and where it was found
+    pub fn last_match(&self, haystack: &[u8]) -> Option<(T, Range<usize>)> {
+        (0..haystack.len()).rev()
+            .find_map(|start| self.match_range(haystack, start))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rstest::rstest;
+
+    use super::*;
+
+    fn digits() -> PatternMatcher<i32> {
+        PatternMatcher::new([("one", 1), ("two", 2), ("three", 3), ("eight", 8), ("1", 1), ("2", 2)])
+    }
+
+    #[rstest]
+    #[case("eightwo", Some(8), Some(2))]
+    #[case("twone", Some(2), Some(1))]
+    #[case("xx1yy", Some(1), Some(1))]
+    #[case("thre", None, None)]
+    #[case("", None, None)]
+    #[case("onethreeight", Some(1), Some(8))]
+    fn test_first_and_last(#[case] input: &str, #[case] first: Option<i32>, #[case] last: Option<i32>) {
+        let matcher = digits();
+        assert_eq!(first, matcher.first_match(input.as_bytes()).map(|(value, _)| value));
+        assert_eq!(last, matcher.last_match(input.as_bytes()).map(|(value, _)| value));
+    }
+
+    #[test]
+    fn test_ranges() {
+        let matcher = digits();
+        assert_eq!(Some((8, 0..5)), matcher.first_match(b"eightwo"));
+        assert_eq!(Some((2, 4..7)), matcher.last_match(b"eightwo"));
+    }
+
+    #[test]
+    fn test_ignore_ascii_case() {
+        let matcher = PatternMatcher::with_case([("One", 1), ("two", 2)], true);
+        assert_eq!(Some((1, 0..3)), matcher.first_match(b"oNE"));
+        assert_eq!(Some((2, 1..4)), matcher.last_match(b"xTWO"));
+        assert_eq!(None, digits().first_match(b"ONE"));
+    }
+
+    #[test]
+    fn test_longest_match() {
+        let matcher = PatternMatcher::new([("a", 1), ("abc", 2), ("ab", 3)]);
+        assert_eq!(Some((2, 3)), matcher.match_at(b"abcd"));
+        assert_eq!(Some((3, 2)), matcher.match_at(b"abd"));
+        assert_eq!(None, matcher.match_at(b"b"));
+    }
+}
@@ -0,0 +1,219 @@
+use std::str::FromStr;
+
+use super::error::AocError;
+
+// a piece of the input together with the 1-based line and column it starts
+// at, every span derived from it keeps track of its own position so errors
+// can point at the offending text
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Span<'a> {
+    pub text: &'a str,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl<'a> Span<'a> {
+    pub fn new(text: &'a str, line: usize) -> Self {
+        Span { text, line, column: 1 }
+    }
+
+    // the whole of a multi-line document, starting at line 1
+    pub fn document(text: &'a str) -> Self {
+        Span::new(text, 1)
+    }
+
+    pub fn error(&self, reason: impl Into<String>) -> AocError {
+        AocError::at(self.line, self.column, reason)
+    }
+
+    // the text between the byte offsets `start` and `end` of this span
+    fn slice(&self, start: usize, end: usize) -> Span<'a> {
+        let prefix = &self.text[..start];
+        let text = &self.text[start..end];
+        match prefix.rfind('\n') {
+            Some(newline) => Span {
+                text,
+                line: self.line + prefix.matches('\n').count(),
+                column: prefix.len() - newline,
+            },
+            None => Span { text, line: self.line, column: self.column + prefix.len() },
+        }
+    }
+
+    fn rest(&self, start: usize) -> Span<'a> {
+        self.slice(start, self.text.len())
+    }
+
+    // byte ranges of the pieces between separators, like `str::split`
+    fn pieces(&self, separator: &'a str) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let mut start = 0;
+        self.text.split(separator).map(move |piece| {
+            let range = (start, start + piece.len());
+            start += piece.len() + separator.len();
+            range
+        })
+    }
+
+    // byte ranges of the lines, without their `\n` or `\r\n`, like `str::lines`
+    fn line_ranges(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let mut start = 0;
+        self.text.split_inclusive('\n').map(move |line| {
+            let text = line.strip_suffix('\n').map(|line| line.strip_suffix('\r').unwrap_or(line)).unwrap_or(line);
+            let range = (start, start + text.len());
+            start += line.len();
+            range
+        })
+    }
+
+    pub fn is_blank(&self) -> bool {
+        self.text.trim().is_empty()
+    }
+
+    #[allow(unused)]
+    pub fn trim(&self) -> Span<'a> {
+        let start = self.text.len() - self.text.trim_start().len();
+        self.slice(start, start.max(self.text.trim_end().len()))
+    }
+
+    pub fn split_once(&self, separator: &str) -> Option<(Span<'a>, Span<'a>)> {
+        let at = self.text.find(separator)?;
+        Some((self.slice(0, at), self.rest(at + separator.len())))
+    }
+
+    pub fn split(&self, separator: &'a str) -> impl Iterator<Item = Span<'a>> + '_ {
+        self.pieces(separator).map(|(start, end)| self.slice(start, end))
+    }
+
+    pub fn words(&self) -> impl Iterator<Item = Span<'a>> + '_ {
+        let mut start = 0;
+        self.text.split(|c: char| c.is_ascii_whitespace())
+            .map(move |word| {
+                let range = (start, start + word.len());
+                start += word.len() + 1;
+                range
+            })
+            .filter(|(start, end)| start < end)
+            .map(|(start, end)| self.slice(start, end))
+    }
+
+    pub fn lines(&self) -> impl Iterator<Item = Span<'a>> + '_ {
+        self.line_ranges().map(|(start, end)| self.slice(start, end))
+    }
+
+    // the text after `label`, if the span starts with it
+    pub fn strip_label(&self, label: &str) -> Option<Span<'a>> {
+        self.text.starts_with(label).then(|| self.rest(label.len()))
+    }
+
+    // `<label> <value>`, such as the `Time:` line of day 6 or the `seeds:` line of day 5
+    pub fn labeled(&self, label: &str) -> Result<Span<'a>, AocError> {
+        self.strip_label(label).ok_or_else(|| {
+            let found = self.text.split_ascii_whitespace().next().unwrap_or("");
+            self.error(format!("expected `{label}`, found `{found}`"))
+        })
+    }
+
+    // `what` names the value in the error, as in "invalid card id `x`"
+    pub fn parse<T: FromStr>(&self, what: &str) -> Result<T, AocError> {
+        T::from_str(self.text)
+            .map_err(|_| self.error(format!("invalid {what} `{}`", self.text)))
+    }
+
+    // whitespace separated values
+    pub fn list<T: FromStr>(&self, what: &str) -> Result<Vec<T>, AocError> {
+        self.words()
+            .map(|word| word.parse(what))
+            .collect()
+    }
+
+    // runs of non-blank lines separated by one or more blank lines
+    pub fn blocks(&self) -> Vec<Span<'a>> {
+        let mut blocks = vec![];
+        let mut block: Option<(usize, usize)> = None;
+        for (start, end) in self.line_ranges() {
+            if self.text[start..end].trim().is_empty() {
+                blocks.extend(block.take().map(|(start, end)| self.slice(start, end)));
+            } else {
+                block = Some((block.map_or(start, |(first, _)| first), end));
+            }
+        }
+        blocks.extend(block.map(|(start, end)| self.slice(start, end)));
+        blocks
+    }
+
+    // a `<key>:` header line followed by the lines of its section
+    pub fn section(&self) -> Result<(Span<'a>, Span<'a>), AocError> {
+        let (header, body) = match self.text.find('\n') {
+            Some(newline) => (&self.text[..newline], self.rest(newline + 1)),
+            None => (self.text, self.rest(self.text.len())),
+        };
+        let key = header.trim_end().strip_suffix(':')
+            .ok_or_else(|| self.error(format!("expected `<key>:`, found `{header}`")))?;
+        Ok((self.slice(0, key.len()), body))
+    }
+}
+
+// how forgiving a day's parser is, each day documents what strict parsing
+// rejects and how lenient parsing recovers
+#[allow(unused)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ParseMode {
+    Strict,
+    Lenient,
+}
+
+#[cfg(test)]
+mod test {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[test]
+    fn test_positions() {
+        let line = Span::new("Card 12: 41 48 | 83", 3);
+        let (header, numbers) = line.split_once(": ").unwrap();
+        assert_eq!(Ok(12), header.labeled("Card").and_then(|id| id.trim().parse::<i32>("card id")));
+        let picked: Vec<Span> = numbers.split(" | ").collect();
+        assert_eq!(Span { text: "83", line: 3, column: 18 }, picked[1]);
+        assert_eq!(Ok(vec![41, 48]), picked[0].list::<i32>("number"));
+    }
+
+    #[test]
+    fn test_offsets() {
+        let document = Span::document("a b\r\n  c  \r\n\r\n");
+        let lines: Vec<Span> = document.lines().collect();
+        assert_eq!(vec!["a b", "  c  ", ""], lines.iter().map(|line| line.text).collect::<Vec<_>>());
+        assert_eq!(Span { text: "c", line: 2, column: 3 }, lines[1].trim());
+        assert_eq!(Span { text: "", line: 3, column: 1 }, lines[2].trim());
+        // repeated text is still placed where it occurs
+        let words: Vec<Span> = Span::new("7 7  7", 1).words().collect();
+        assert_eq!(vec![1, 3, 6], words.iter().map(|word| word.column).collect::<Vec<_>>());
+    }
+
+    #[rstest]
+    #[case("Time: 7 x", AocError::at(1, 9, "invalid number `x`"))]
+    #[case("Tim: 7", AocError::at(1, 1, "expected `Time:`, found `Tim:`"))]
+    fn test_errors(#[case] input: &str, #[case] expected: AocError) {
+        let result = Span::document(input).labeled("Time:")
+            .and_then(|values| values.list::<i64>("number"));
+        assert_eq!(Err(expected), result);
+    }
+
+    #[test]
+    fn test_blocks_and_sections() {
+        let document = Span::document("seeds: 79 14\n\n\nsoil map:\n50 98 2\n  52 50 48\n\nlight map:\n");
+        let blocks = document.blocks();
+        assert_eq!(3, blocks.len());
+        assert_eq!(Span { text: "soil map:\n50 98 2\n  52 50 48", line: 4, column: 1 }, blocks[1]);
+
+        let (key, body) = blocks[1].section().unwrap();
+        assert_eq!(Span { text: "soil map", line: 4, column: 1 }, key);
+        let rows: Vec<Span> = body.lines().collect();
+        assert_eq!(Span { text: "  52 50 48", line: 6, column: 1 }, rows[1]);
+        assert_eq!(Some(Span { text: "50", line: 6, column: 6 }), rows[1].words().nth(1));
+
+        let (key, body) = blocks[2].section().unwrap();
+        assert_eq!(("light map", ""), (key.text, body.text));
+        assert_eq!(Err(AocError::at(1, 1, "expected `<key>:`, found `seeds: 79 14`")), blocks[0].section());
+    }
+}
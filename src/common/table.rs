@@ -0,0 +1,34 @@
+// left aligned columns separated by two spaces, the first row being the header
+pub fn render(rows: &[Vec<String>]) -> String {
+    let mut widths: Vec<usize> = vec![];
+    for row in rows {
+        widths.resize(widths.len().max(row.len()), 0);
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+    let mut table = String::new();
+    for row in rows {
+        let cells: Vec<String> = row.iter().zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect();
+        table.push_str(cells.join("  ").trim_end());
+        table.push('\n');
+    }
+    table
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let rows = vec![
+            vec!["day".to_string(), "answer".to_string(), "error".to_string()],
+            vec!["12".to_string(), "7".to_string(), String::new()],
+        ];
+        assert_eq!("day  answer  error\n12   7\n", render(&rows));
+        assert_eq!("", render(&[]));
+    }
+}
@@ -0,0 +1,60 @@
+use std::sync::OnceLock;
+
+use crate::common::{bytes::lines, error::AocError, matcher::PatternMatcher};
+
+fn spelled_digits() -> &'static PatternMatcher<u64> {
+    static MATCHER: OnceLock<PatternMatcher<u64>> = OnceLock::new();
+    MATCHER.get_or_init(|| {
+        let words = ["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+        let digits = ["1", "2", "3", "4", "5", "6", "7", "8", "9"];
+        PatternMatcher::new(words.into_iter().zip(1..).chain(digits.into_iter().zip(1..)))
+    })
+}
+
+fn sum_lines(input: &[u8], calibration: impl Fn(&[u8]) -> Option<u64>) -> Result<u64, AocError> {
+    lines(input).enumerate().try_fold(0, |sum, (i, line)| {
+        let value = calibration(line)
+            .ok_or_else(|| AocError::at(i + 1, 1, "no calibration digits"))?;
+        Ok(sum + value)
+    })
+}
+
+pub fn solve_easy(input: &[u8]) -> Result<u64, AocError> {
+    sum_lines(input, |line| {
+        let first = line.iter().find(|b| b.is_ascii_digit())?;
+        let last = line.iter().rfind(|b| b.is_ascii_digit())?;
+        Some(10 * (first - b'0') as u64 + (last - b'0') as u64)
+    })
+}
+
+pub fn solve_hard(input: &[u8]) -> Result<u64, AocError> {
+    let matcher = spelled_digits();
+    sum_lines(input, |line| {
+        let (first, _) = matcher.first_match(line)?;
+        let (last, _) = matcher.last_match(line)?;
+        Some(10 * first + last)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_testcase() {
+        assert_eq!(Ok(142), solve_easy(&std::fs::read("input/day_01/easy_test.txt").unwrap()));
+        assert_eq!(Ok(281), solve_hard(&std::fs::read("input/day_01/hard_test.txt").unwrap()));
+    }
+
+    #[test]
+    fn test_solve() {
+        let input = std::fs::read("input/day_01/puzzle.txt").unwrap();
+        assert_eq!(Ok(55108), solve_easy(&input));
+        assert_eq!(Ok(56324), solve_hard(&input));
+    }
+
+    #[test]
+    fn test_missing_digits() {
+        assert_eq!(Err(AocError::at(2, 1, "no calibration digits")), solve_easy(b"1\nab\n"));
+    }
+}
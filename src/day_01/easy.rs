@@ -1,21 +1,16 @@
-use crate::common::{error::AocError, functions::file_to_lines};
-use crate::common::traits::ToBaseTen;
-
-fn parse_line(line: String) -> Option<i32> {
-    let remaining: String = line.chars()
-        .filter(char::is_ascii_digit)
-        .collect();
-    let first = remaining.chars().next().and_then(char::to_base_10)?;
-    let last = remaining.chars().last().and_then(char::to_base_10)?;
-    Some(10*first + last)
-}
+use std::sync::OnceLock;
+
+use crate::common::error::AocError;
 
-fn sum_file(filename: &str) -> Result<i32, AocError> {
-    let answer: i32 = file_to_lines(filename)?
-        .filter_map(|line| parse_line(line.ok()?))
-        .sum();
-    Ok(answer)
+use super::{report::{MissingDigits, sum_file_with}, vocabulary::{CalibrationExtractor, Vocabulary}};
+
+fn extractor() -> &'static CalibrationExtractor {
+    static EXTRACTOR: OnceLock<CalibrationExtractor> = OnceLock::new();
+    EXTRACTOR.get_or_init(|| CalibrationExtractor::new(&Vocabulary::digits_only()))
+}
 
+pub fn sum_file(filename: &str) -> Result<i32, AocError> {
+    sum_file_with(filename, extractor(), MissingDigits::Error)
 }
 
 #[allow(unused)]
@@ -46,7 +41,8 @@ mod test {
     #[case("a1b2c3d4e5f", 15)]
     #[case("treb7uchet", 77)]
     fn test_cases(#[case] input: &str, #[case] expected: i32) {
-        assert_eq!(Some(expected), parse_line(input.into()));
+        let calibration = extractor().extract(input).map(|calibration| calibration.value());
+        assert_eq!(Some(expected), calibration);
     }
 
 }
\ No newline at end of file
@@ -1,50 +1,16 @@
-use crate::common::{error::AocError, functions::file_to_lines};
-
-fn check_digit_strings(line: &[u8]) -> Option<i32> {
-    let patterns = ["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
-    patterns.into_iter().enumerate()
-        .filter_map(|(i, pattern)| {
-            if line.starts_with(pattern.as_bytes()) {
-                Some(i as i32 +1)
-            } else {
-                None
-            }
-        })
-        .next()
-}
-
-fn starts_with_digit(line: &[u8]) -> Option<i32> {
-    let c = line.first()?;
-    match c {
-        b'1'..=b'9' => Some((c - b'0') as i32),
-        _ => check_digit_strings(line)
-    }
-}
+use std::sync::OnceLock;
 
-fn find_all_digits(line: &String) -> Vec<i32> {
-    let line = line.as_bytes();
+use crate::common::error::AocError;
 
-    line.iter().enumerate()
-    .filter_map(|(i, _)| starts_with_digit(&line[i..]))
-    .collect()
+use super::{report::{MissingDigits, sum_file_with}, vocabulary::{CalibrationExtractor, Vocabulary}};
 
+fn extractor() -> &'static CalibrationExtractor {
+    static EXTRACTOR: OnceLock<CalibrationExtractor> = OnceLock::new();
+    EXTRACTOR.get_or_init(|| CalibrationExtractor::new(&Vocabulary::english()))
 }
 
-fn line_to_digit(line: String) -> Option<i32> {
-    let matches = find_all_digits(&line);
-    let first = matches.first()?;
-    let last = matches.last()?;
-    Some(10 * first + last)
-}
-
-fn sum_file(filename: &str) -> Result<i32, AocError> {
-    let sum: i32 = file_to_lines(filename)?
-        .flatten()
-        .filter_map(line_to_digit)
-        .sum();
-
-    Ok(sum)
-
+pub fn sum_file(filename: &str) -> Result<i32, AocError> {
+    sum_file_with(filename, extractor(), MissingDigits::Error)
 }
 
 #[allow(unused)]
@@ -56,8 +22,15 @@ pub fn solve() -> Result<i32, AocError> {
 mod test {
     use rstest::rstest;
 
+    use crate::common::bench::measure;
+
     use super::*;
 
+    fn line_to_digit(line: &str) -> Option<i32> {
+        extractor().extract(line)
+            .map(|calibration| calibration.value())
+    }
+
     #[test]
     fn test_testcase() {
         assert_eq!(Ok(281), sum_file("input/day_01/hard_test.txt"))
@@ -76,7 +49,60 @@ mod test {
     #[case("4nineeightseven2", 42)]
     #[case("zoneight234", 14)]
     #[case("7pqrstsixteen", 76)]
+    #[case("eightwo", 82)]
+    #[case("twone", 21)]
     fn test_cases(#[case] input: &str, #[case] expected: i32) {
-        assert_eq!(Some(expected), line_to_digit(input.into()));
+        assert_eq!(Some(expected), line_to_digit(input));
+    }
+
+    // the original approach, kept as a baseline for the benchmark
+    fn check_digit_strings(line: &[u8]) -> Option<i32> {
+        let patterns = ["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+        patterns.into_iter().enumerate()
+            .filter_map(|(i, pattern)| {
+                if line.starts_with(pattern.as_bytes()) {
+                    Some(i as i32 +1)
+                } else {
+                    None
+                }
+            })
+            .next()
+    }
+
+    fn starts_with_digit(line: &[u8]) -> Option<i32> {
+        let c = line.first()?;
+        match c {
+            b'1'..=b'9' => Some((c - b'0') as i32),
+            _ => check_digit_strings(line)
+        }
+    }
+
+    fn baseline_line_to_digit(line: &str) -> Option<i32> {
+        let line = line.as_bytes();
+        let matches: Vec<i32> = line.iter().enumerate()
+            .filter_map(|(i, _)| starts_with_digit(&line[i..]))
+            .collect();
+        Some(10 * matches.first()? + matches.last()?)
+    }
+
+    #[test]
+    #[ignore = "benchmark, run with --release"]
+    fn bench_matcher() {
+        let puzzle = std::fs::read_to_string("input/day_01/puzzle.txt").unwrap();
+        let lines: Vec<String> = puzzle.lines()
+            .cycle()
+            .take(1_000_000)
+            .map(str::to_string)
+            .collect();
+
+        let (baseline, baseline_time) = measure("starts_with at every offset", || {
+            lines.iter().filter_map(|line| baseline_line_to_digit(line)).sum::<i32>()
+        });
+        let (matched, matcher_time) = measure("trie matcher", || {
+            lines.iter().filter_map(|line| line_to_digit(line)).sum::<i32>()
+        });
+
+        assert_eq!(baseline, matched);
+        println!("speedup: {:.1}x", baseline_time.as_secs_f64() / matcher_time.as_secs_f64());
     }
 }
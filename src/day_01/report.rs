@@ -0,0 +1,114 @@
+use crate::common::{error::AocError, functions::{file_to_lines, unreadable_line}};
+
+use super::vocabulary::CalibrationExtractor;
+
+#[allow(unused)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MissingDigits {
+    Skip,
+    // skip the line, but print it to stderr
+    Warn,
+    Error,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct LineReport {
+    pub line: usize,
+    pub first: Option<i32>,
+    pub last: Option<i32>,
+}
+
+impl LineReport {
+    pub fn value(&self) -> Option<i32> {
+        Some(10 * self.first? + self.last?)
+    }
+}
+
+// a line that cannot be read is an error rather than silently skipped or
+// ending the document early
+pub fn report_lines(filename: &str, extractor: &CalibrationExtractor) -> Result<Vec<LineReport>, AocError> {
+    file_to_lines(filename)?
+        .enumerate()
+        .map(|(i, line)| {
+            let line = line.map_err(|error| unreadable_line(i + 1, error))?;
+            let calibration = extractor.extract(&line);
+            Ok(LineReport {
+                line: i + 1,
+                first: calibration.map(|c| c.first.value),
+                last: calibration.map(|c| c.last.value),
+            })
+        })
+        .collect()
+}
+
+pub fn sum_reports(reports: &[LineReport], policy: MissingDigits) -> Result<i32, AocError> {
+    let mut sum = 0;
+    for report in reports {
+        match (report.value(), policy) {
+            (Some(value), _) => sum += value,
+            (None, MissingDigits::Skip) => {},
+            (None, MissingDigits::Warn) => eprintln!("line {}: no calibration digits", report.line),
+            (None, MissingDigits::Error) => Err(AocError::at(report.line, 1, "no calibration digits"))?,
+        }
+    }
+    Ok(sum)
+}
+
+pub fn sum_file_with(filename: &str, extractor: &CalibrationExtractor, policy: MissingDigits) -> Result<i32, AocError> {
+    let reports = report_lines(filename, extractor)?;
+    sum_reports(&reports, policy)
+}
+
+#[cfg(test)]
+mod test {
+    use rstest::rstest;
+
+    use crate::day_01::vocabulary::Vocabulary;
+
+    use super::*;
+
+    fn english() -> CalibrationExtractor {
+        CalibrationExtractor::new(&Vocabulary::english())
+    }
+
+    #[test]
+    fn test_vocabularies() {
+        let digits = CalibrationExtractor::new(&Vocabulary::digits_only());
+        assert_eq!(Ok(142), sum_file_with("input/day_01/easy_test.txt", &digits, MissingDigits::Error));
+        assert_eq!(Ok(281), sum_file_with("input/day_01/hard_test.txt", &english(), MissingDigits::Error));
+    }
+
+    #[test]
+    fn test_report() {
+        let reports = report_lines("input/day_01/missing_digits_test.txt", &english()).unwrap();
+        assert_eq!(vec![
+            LineReport { line: 1, first: Some(1), last: Some(2) },
+            LineReport { line: 2, first: None, last: None },
+            LineReport { line: 3, first: Some(7), last: Some(7) },
+        ], reports);
+    }
+
+    #[rstest]
+    #[case(MissingDigits::Skip, Ok(89))]
+    #[case(MissingDigits::Warn, Ok(89))]
+    #[case(MissingDigits::Error, Err(AocError::at(2, 1, "no calibration digits")))]
+    fn test_policies(#[case] policy: MissingDigits, #[case] expected: Result<i32, AocError>) {
+        let filename = "input/day_01/missing_digits_test.txt";
+        assert_eq!(expected, sum_file_with(filename, &english(), policy));
+    }
+
+    #[test]
+    fn test_missing_file() {
+        let result = sum_file_with("input/day_01/missing.txt", &english(), MissingDigits::Skip);
+        assert_eq!(Err(AocError::FileNotFound), result);
+    }
+
+    #[test]
+    fn test_unreadable_line() {
+        let path = std::env::temp_dir().join(format!("aoc_2023_unreadable_{}.txt", std::process::id()));
+        std::fs::write(&path, b"1abc2\n\xff3\n4ab5\n").unwrap();
+        let result = sum_file_with(path.to_str().unwrap(), &english(), MissingDigits::Skip);
+        assert_eq!(Err(AocError::at(2, 1, "line is not valid UTF-8")), result);
+        std::fs::remove_file(path).unwrap();
+    }
+}
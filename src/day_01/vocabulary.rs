@@ -0,0 +1,155 @@
+use crate::common::matcher::PatternMatcher;
+
+// the character for zero in each block of Unicode decimal digits we accept,
+// the other nine digits follow it contiguously
+const UNICODE_ZEROS: [char; 6] = ['0', '\u{0660}', '\u{06F0}', '\u{0966}', '\u{09E6}', '\u{FF10}'];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Vocabulary {
+    pub words: Vec<(String, i32)>,
+    // ASCII letters are folded directly, other words are also matched in
+    // their all-lowercase, all-uppercase and capitalized forms
+    pub case_insensitive: bool,
+    pub include_zero: bool,
+    pub unicode_digits: bool,
+}
+
+impl Vocabulary {
+    pub fn english() -> Self {
+        let words = ["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+        Vocabulary {
+            words: words.into_iter().zip(1..).map(|(word, value)| (word.to_string(), value)).collect(),
+            case_insensitive: false,
+            include_zero: false,
+            unicode_digits: false,
+        }
+    }
+
+    pub fn digits_only() -> Self {
+        Vocabulary {
+            words: vec![],
+            case_insensitive: false,
+            include_zero: true,
+            unicode_digits: false,
+        }
+    }
+
+    fn digits(&self) -> Vec<(String, i32)> {
+        let zeros: &[char] = if self.unicode_digits { &UNICODE_ZEROS } else { &UNICODE_ZEROS[..1] };
+        let first = if self.include_zero { 0 } else { 1 };
+        zeros.iter()
+            .flat_map(|zero| (first..10).map(move |value| (*zero as u32 + value, value as i32)))
+            .filter_map(|(code, value)| Some((char::from_u32(code)?.to_string(), value)))
+            .collect()
+    }
+
+    fn word_forms(&self) -> Vec<(String, i32)> {
+        if !self.case_insensitive {
+            return self.words.clone();
+        }
+        self.words.iter()
+            .flat_map(|(word, value)| {
+                let mut chars = word.chars();
+                let capitalized: String = chars.next().into_iter()
+                    .flat_map(char::to_uppercase)
+                    .chain(chars.flat_map(char::to_lowercase))
+                    .collect();
+                [word.clone(), word.to_lowercase(), word.to_uppercase(), capitalized]
+                    .map(|form| (form, *value))
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DigitToken<'a> {
+    pub value: i32,
+    pub token: &'a str,
+    pub start: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Calibration<'a> {
+    pub first: DigitToken<'a>,
+    pub last: DigitToken<'a>,
+}
+
+impl Calibration<'_> {
+    #[allow(unused)]
+    pub fn value(&self) -> i32 {
+        10 * self.first.value + self.last.value
+    }
+}
+
+pub struct CalibrationExtractor {
+    matcher: PatternMatcher<i32>,
+}
+
+impl CalibrationExtractor {
+    pub fn new(vocabulary: &Vocabulary) -> Self {
+        let patterns: Vec<(String, i32)> = vocabulary.word_forms().into_iter()
+            .chain(vocabulary.digits())
+            .collect();
+        let patterns = patterns.iter().map(|(pattern, value)| (pattern.as_str(), *value));
+        let matcher = PatternMatcher::with_case(patterns, vocabulary.case_insensitive);
+        CalibrationExtractor { matcher }
+    }
+
+    pub fn extract<'a>(&self, line: &'a str) -> Option<Calibration<'a>> {
+        let to_token = |(value, range): (i32, std::ops::Range<usize>)| DigitToken {
+            value,
+            start: range.start,
+            token: &line[range],
+        };
+        let first = self.matcher.first_match(line.as_bytes()).map(to_token)?;
+        let last = self.matcher.last_match(line.as_bytes()).map(to_token)?;
+        Some(Calibration { first, last })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rstest::rstest;
+
+    use super::*;
+
+    fn german() -> Vocabulary {
+        let words = ["null", "eins", "zwei", "drei", "vier", "fünf", "sechs", "sieben", "acht", "neun"];
+        Vocabulary {
+            words: words.into_iter().zip(0..).map(|(word, value)| (word.to_string(), value)).collect(),
+            case_insensitive: true,
+            include_zero: true,
+            unicode_digits: true,
+        }
+    }
+
+    #[test]
+    fn test_tokens() {
+        let extractor = CalibrationExtractor::new(&Vocabulary::english());
+        let calibration = extractor.extract("xtwone3four").unwrap();
+        assert_eq!(DigitToken { value: 2, token: "two", start: 1 }, calibration.first);
+        assert_eq!(DigitToken { value: 4, token: "four", start: 7 }, calibration.last);
+        assert_eq!(24, calibration.value());
+        assert_eq!(None, extractor.extract("zero"));
+    }
+
+    #[rstest]
+    #[case("FÜNFxx0", 50, "FÜNF", "0")]
+    #[case("Fünf and ７", 57, "Fünf", "７")]
+    #[case("nullachtzehn", 8, "null", "acht")]
+    #[case("a\u{0663}b", 33, "\u{0663}", "\u{0663}")]
+    #[case("DreiEins", 31, "Drei", "Eins")]
+    fn test_configured(#[case] input: &str, #[case] expected: i32, #[case] first: &str, #[case] last: &str) {
+        let extractor = CalibrationExtractor::new(&german());
+        let calibration = extractor.extract(input).unwrap();
+        assert_eq!(expected, calibration.value());
+        assert_eq!(first, calibration.first.token);
+        assert_eq!(last, calibration.last.token);
+    }
+
+    #[test]
+    fn test_unicode_digits_disabled() {
+        let extractor = CalibrationExtractor::new(&Vocabulary::english());
+        assert_eq!(None, extractor.extract("７"));
+    }
+}
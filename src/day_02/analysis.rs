@@ -0,0 +1,108 @@
+use std::collections::BTreeMap;
+
+use super::common::{Color, CubeSet, Game, combine_cube_sets};
+
+// games that are possible with a limit of `below`, but not with any lower limit
+#[derive(Debug, PartialEq)]
+pub struct Cutoff {
+    pub below: i32,
+    pub games: Vec<i32>,
+}
+
+fn max_cube_set<'a>(games: impl Iterator<Item = &'a Game>) -> CubeSet {
+    games.flat_map(|game| game.picks.iter().cloned())
+        .fold(CubeSet::default(), combine_cube_sets)
+}
+
+pub fn minimal_bag(games: &[Game]) -> CubeSet {
+    max_cube_set(games.iter())
+}
+
+// returns None if any of the requested games does not exist
+#[allow(unused)]
+pub fn tightest_bag_for(games: &[Game], ids: &[i32]) -> Option<CubeSet> {
+    let selected: Vec<&Game> = ids.iter()
+        .map(|id| games.iter().find(|game| game.id.0 == *id))
+        .collect::<Option<_>>()?;
+    Some(max_cube_set(selected.into_iter()))
+}
+
+// for every colour, how many picks showed a given (non-zero) count
+#[allow(unused)]
+pub fn color_distributions(games: &[Game]) -> BTreeMap<Color, BTreeMap<i32, usize>> {
+    let mut distributions: BTreeMap<Color, BTreeMap<i32, usize>> = BTreeMap::new();
+    for pick in games.iter().flat_map(|game| game.picks.iter()) {
+        for (color, count) in pick.counts.iter() {
+            *distributions.entry(color.clone())
+                .or_default()
+                .entry(*count)
+                .or_insert(0) += 1;
+        }
+    }
+    distributions
+}
+
+fn color_cutoffs(games: &[Game], color: &Color) -> Vec<Cutoff> {
+    let mut by_max: BTreeMap<i32, Vec<i32>> = BTreeMap::new();
+    for game in games {
+        let max = max_cube_set(std::iter::once(game)).count(color);
+        if max > 0 {
+            by_max.entry(max).or_default().push(game.id.0);
+        }
+    }
+    by_max.into_iter()
+        .rev()
+        .map(|(below, games)| Cutoff { below, games })
+        .collect()
+}
+
+// lowering a single colour limit from the minimal bag, in which order do games become impossible
+#[allow(unused)]
+pub fn impossible_as_limits_lower(games: &[Game]) -> BTreeMap<Color, Vec<Cutoff>> {
+    minimal_bag(games).colors()
+        .map(|color| (color.clone(), color_cutoffs(games, color)))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::common::get_games;
+
+    #[test]
+    fn test_minimal_bag() {
+        let games = get_games("input/day_02/easy_test.txt").unwrap();
+        assert_eq!(CubeSet::rgb(20, 13, 15), minimal_bag(&games));
+    }
+
+    #[test]
+    fn test_tightest_bag() {
+        let games = get_games("input/day_02/easy_test.txt").unwrap();
+        assert_eq!(Some(CubeSet::rgb(6, 3, 6)), tightest_bag_for(&games, &[1, 2, 5]));
+        assert_eq!(Some(CubeSet::default()), tightest_bag_for(&games, &[]));
+        assert_eq!(None, tightest_bag_for(&games, &[1, 6]));
+    }
+
+    #[test]
+    fn test_distributions() {
+        let games = get_games("input/day_02/easy_test.txt").unwrap();
+        let distributions = color_distributions(&games);
+        let expected = BTreeMap::from([(1, 4), (3, 1), (4, 2), (6, 2), (14, 1), (20, 1)]);
+        assert_eq!(Some(&expected), distributions.get(&"red".into()));
+    }
+
+    #[test]
+    fn test_cutoffs() {
+        let games = get_games("input/day_02/easy_test.txt").unwrap();
+        let cutoffs = impossible_as_limits_lower(&games);
+        let expected = vec![
+            Cutoff { below: 20, games: vec![3] },
+            Cutoff { below: 14, games: vec![4] },
+            Cutoff { below: 6, games: vec![5] },
+            Cutoff { below: 4, games: vec![1] },
+            Cutoff { below: 1, games: vec![2] },
+        ];
+        assert_eq!(Some(&expected), cutoffs.get(&"red".into()));
+        assert_eq!(3, cutoffs.len());
+    }
+}
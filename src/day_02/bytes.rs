@@ -0,0 +1,78 @@
+use crate::common::{bytes::{lines, parse_u64}, error::AocError};
+
+// the largest count of each colour over all picks of a game, which is all
+// either part needs, so picks are never materialised
+#[derive(Debug, Default, PartialEq)]
+struct Maxima {
+    red: u64,
+    green: u64,
+    blue: u64,
+}
+
+// Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+fn parse_game(line: &[u8], line_number: usize) -> Result<(u64, Maxima), AocError> {
+    let invalid = |reason: &str| AocError::at(line_number, 1, reason);
+    let rest = line.strip_prefix(b"Game ").ok_or_else(|| invalid("expected `Game <id>`"))?;
+    let colon = rest.iter().position(|b| *b == b':').ok_or_else(|| invalid("expected `Game <id>: <picks>`"))?;
+    let id = parse_u64(&rest[..colon]).map_err(|_| invalid("invalid game id"))?;
+
+    let mut maxima = Maxima::default();
+    for part in rest[colon + 1..].split(|b| *b == b',' || *b == b';') {
+        let part = part.strip_prefix(b" ").unwrap_or(part);
+        let space = part.iter().position(|b| *b == b' ').ok_or_else(|| invalid("expected `<count> <colour>`"))?;
+        let count = parse_u64(&part[..space]).map_err(|_| invalid("invalid count"))?;
+        let max = match &part[space + 1..] {
+            b"red" => &mut maxima.red,
+            b"green" => &mut maxima.green,
+            b"blue" => &mut maxima.blue,
+            _ => return Err(invalid("unknown colour")),
+        };
+        *max = (*max).max(count);
+    }
+    Ok((id, maxima))
+}
+
+fn games(input: &[u8]) -> impl Iterator<Item = Result<(u64, Maxima), AocError>> + '_ {
+    lines(input).enumerate()
+        .map(|(i, line)| parse_game(line, i + 1))
+}
+
+pub fn solve_easy(input: &[u8]) -> Result<u64, AocError> {
+    games(input).try_fold(0, |sum, game| {
+        let (id, maxima) = game?;
+        let possible = maxima.red <= 12 && maxima.green <= 13 && maxima.blue <= 14;
+        Ok(if possible { sum + id } else { sum })
+    })
+}
+
+pub fn solve_hard(input: &[u8]) -> Result<u64, AocError> {
+    games(input).try_fold(0, |sum, game| {
+        let (_, maxima) = game?;
+        Ok(sum + maxima.red * maxima.green * maxima.blue)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_testcase() {
+        let input = std::fs::read("input/day_02/easy_test.txt").unwrap();
+        assert_eq!(Ok(8), solve_easy(&input));
+        assert_eq!(Ok(2286), solve_hard(&input));
+    }
+
+    #[test]
+    fn test_solve() {
+        let input = std::fs::read("input/day_02/puzzle.txt").unwrap();
+        assert_eq!(Ok(2541), solve_easy(&input));
+        assert_eq!(Ok(66016), solve_hard(&input));
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert_eq!(Err(AocError::at(1, 1, "unknown colour")), solve_easy(b"Game 1: 3 pink"));
+        assert_eq!(Ok((4, Maxima { red: 2, green: 0, blue: 5 })), parse_game(b"Game 4: 2 red; 5 blue, 1 red", 1));
+    }
+}
@@ -1,6 +1,6 @@
-use std::str::FromStr;
+use std::{collections::{BTreeMap, HashSet}, str::FromStr};
 
-use crate::common::error::AocError;
+use crate::common::{error::AocError, functions::{file_to_lines, unreadable_line}, parsing::{ParseMode, Span}};
 
 
 
@@ -13,30 +13,141 @@ pub struct Game {
     pub picks: Vec<CubeSet>,
 }
 
-#[derive(Debug, Default, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Color(pub String);
+
+impl From<&str> for Color {
+    fn from(value: &str) -> Self {
+        Color(value.to_string())
+    }
+}
+
+// colours with a count of zero are never stored, so two sets compare equal
+// regardless of whether a colour was omitted or given as "0 red"
+#[derive(Debug, Default, Clone, PartialEq)]
 pub struct CubeSet {
-    pub red: i32,
-    pub green: i32,
-    pub blue: i32,
+    pub counts: BTreeMap<Color, i32>,
+}
+
+impl CubeSet {
+    pub fn rgb(red: i32, green: i32, blue: i32) -> Self {
+        let mut cube_set = CubeSet::default();
+        cube_set.set("red".into(), red);
+        cube_set.set("green".into(), green);
+        cube_set.set("blue".into(), blue);
+        cube_set
+    }
+
+    pub fn set(&mut self, color: Color, count: i32) {
+        if count == 0 {
+            self.counts.remove(&color);
+        } else {
+            self.counts.insert(color, count);
+        }
+    }
+
+    pub fn count(&self, color: &Color) -> i32 {
+        self.counts.get(color).copied().unwrap_or(0)
+    }
+
+    pub fn total(&self) -> i32 {
+        self.counts.values().sum()
+    }
+
+    pub fn colors(&self) -> impl Iterator<Item = &Color> {
+        self.counts.keys()
+    }
+}
+
+pub fn combine_cube_sets(a: CubeSet, b: CubeSet) -> CubeSet {
+    let mut combined = a;
+    for (color, count) in b.counts {
+        let max = i32::max(combined.count(&color), count);
+        combined.set(color, max);
+    }
+    combined
+}
+
+#[allow(unused)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UnknownColors {
+    Reject,
+    Accept,
+}
+
+// strict parsing rejects repeated colours within a pick and game ids that
+// are not 1, 2, 3, ... in file order, lenient parsing sums repeated colours
+// and accepts any ids
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseOptions {
+    pub colors: Vec<Color>,
+    pub unknown_colors: UnknownColors,
+    pub mode: ParseMode,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        ParseOptions {
+            colors: vec!["red".into(), "green".into(), "blue".into()],
+            unknown_colors: UnknownColors::Reject,
+            mode: ParseMode::Strict,
+        }
+    }
+}
+
+impl ParseOptions {
+    fn check_color(&self, color: &str) -> Result<Color, AocError> {
+        let color = Color::from(color);
+        match self.unknown_colors {
+            UnknownColors::Reject if !self.colors.contains(&color) => {
+                Err(AocError::UnknownColor { color: color.0, context: String::new() })
+            },
+            UnknownColors::Reject | UnknownColors::Accept => Ok(color),
+        }
+    }
+}
+
+fn with_context(error: AocError, context: &str) -> AocError {
+    match error {
+        AocError::UnknownColor { color, .. } => AocError::UnknownColor { color, context: context.to_string() },
+        error => error,
+    }
+}
+
+impl Game {
+    pub fn parse_with(s: &str, options: &ParseOptions) -> Result<Self, AocError> {
+        Game::parse_line(s, 1, options)
+    }
+
+    // Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+    pub fn parse_line(s: &str, line: usize, options: &ParseOptions) -> Result<Self, AocError> {
+        let span = Span::new(s, line);
+        let (header, rest) = span.split_once(": ")
+            .ok_or_else(|| span.error("expected `Game <id>: <picks>`"))?;
+        let id = GameId::parse_header(header)?;
+        let cube_sets: Result<Vec<CubeSet>, AocError> = rest.split("; ")
+            .map(|pick| CubeSet::parse_pick(pick, options))
+            .collect();
+        Ok(Game {
+            id,
+            picks: cube_sets.map_err(|error| with_context(error, s))?,
+        })
+    }
 }
 
 impl FromStr for Game {
     type Err = AocError;
 
-    // Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let parts: Vec<&str> = s.split(": ").collect();
-        match parts.as_slice() {
-            [header, rest] => {
-                let cube_sets: Result<Vec<CubeSet>, AocError> = rest.split("; ")
-                    .map(CubeSet::from_str)
-                    .collect();
-                Ok(Game {
-                    id: GameId::from_str(header)?,
-                    picks: cube_sets?,
-                })
-            },
-            _ => Err(AocError::ParseError),
+        Game::parse_with(s, &ParseOptions::default())
+    }
+}
+
+impl GameId {
+    fn parse_header(header: Span) -> Result<Self, AocError> {
+        match header.split_once(" ") {
+            Some((game, id)) if game.text == "Game" => Ok(GameId(id.parse("game id")?)),
+            _ => Err(header.error(format!("expected `Game <id>`, found `{}`", header.text))),
         }
     }
 }
@@ -45,33 +156,85 @@ impl FromStr for GameId {
     type Err = AocError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let id_part = s.split(' ').last().ok_or(AocError::ParseError)?;
-        let id = i32::from_str(id_part).map_err(|_| AocError::ParseError)?;
-        Ok(GameId(id))
+        GameId::parse_header(Span::new(s, 1))
     }
 }
 
-impl FromStr for CubeSet {
-    type Err = AocError;
+fn parse_count(count: Span) -> Result<i32, AocError> {
+    let value = count.parse("count")?;
+    if value < 0 {
+        Err(count.error(format!("negative count `{}`", count.text)))
+    } else {
+        Ok(value)
+    }
+}
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let parts = s.split(", ");
+impl CubeSet {
+    pub fn parse_with(s: &str, options: &ParseOptions) -> Result<Self, AocError> {
+        CubeSet::parse_pick(Span::new(s, 1), options)
+    }
+
+    fn parse_pick(pick: Span, options: &ParseOptions) -> Result<Self, AocError> {
+        if pick.is_blank() {
+            return Err(pick.error("empty pick"));
+        }
         let mut cube_set = CubeSet::default();
-        for part in parts {
-            let set: Vec<&str> = part.split(' ').collect();
+        let mut seen: Vec<Color> = vec![];
+        for part in pick.split(", ") {
+            let set: Vec<Span> = part.split(" ").collect();
             match set.as_slice() {
                 [num, color] => {
-                    let num = i32::from_str(num).map_err(|_| AocError::ParseError)?;
-                    match *color {
-                        "red" => cube_set.red = num,
-                        "green" => cube_set.green = num,
-                        "blue" => cube_set.blue = num,
-                        _ => Err(AocError::ParseError)?
+                    let num = parse_count(*num)?;
+                    let color = options.check_color(color.text)
+                        .map_err(|error| with_context(error, pick.text))?;
+                    if seen.contains(&color) && options.mode == ParseMode::Strict {
+                        return Err(part.error(format!("duplicate colour `{}`", color.0)));
                     }
+                    seen.push(color.clone());
+                    let total = cube_set.count(&color) + num;
+                    cube_set.set(color, total);
                 },
-                _ => Err(AocError::ParseError)?
+                _ => return Err(part.error(format!("expected `<count> <colour>`, found `{}`", part.text))),
             }
         }
         Ok(cube_set)
     }
 }
+
+impl FromStr for CubeSet {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        CubeSet::parse_with(s, &ParseOptions::default())
+    }
+}
+
+// the id is always at column 6, right after "Game "
+fn check_game_ids(games: &[Game]) -> Result<(), AocError> {
+    let mut seen = HashSet::new();
+    for (i, game) in games.iter().enumerate() {
+        let (line, id) = (i + 1, game.id.0);
+        if !seen.insert(id) {
+            return Err(AocError::at(line, 6, format!("duplicate game id {id}")));
+        }
+        if id != line as i32 {
+            return Err(AocError::at(line, 6, format!("expected game id {line}, found {id}")));
+        }
+    }
+    Ok(())
+}
+
+pub fn get_games_with(filename: &str, options: &ParseOptions) -> Result<Vec<Game>, AocError> {
+    let games: Vec<Game> = file_to_lines(filename)?
+        .enumerate()
+        .map(|(i, s)| Game::parse_line(s.map_err(|error| unreadable_line(i + 1, error))?.as_str(), i + 1, options))
+        .collect::<Result<_, _>>()?;
+    if options.mode == ParseMode::Strict {
+        check_game_ids(&games)?;
+    }
+    Ok(games)
+}
+
+pub fn get_games(filename: &str) -> Result<Vec<Game>, AocError> {
+    get_games_with(filename, &ParseOptions::default())
+}
@@ -0,0 +1,160 @@
+use super::common::{Color, CubeSet, Game, ParseOptions};
+
+// every limit is checked against each individual pick of a game
+#[allow(unused)]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Limit {
+    AtLeast(Color, i32),
+    AtMost(Color, i32),
+    TotalAtLeast(i32),
+    TotalAtMost(i32),
+    OnlyColors(Vec<Color>),
+}
+
+impl Limit {
+    // returns the offending colour (if any) and the count that broke the limit
+    fn check(&self, pick: &CubeSet) -> Option<(Option<Color>, i32)> {
+        match self {
+            Limit::AtLeast(color, min) => {
+                let found = pick.count(color);
+                (found < *min).then(|| (Some(color.clone()), found))
+            },
+            Limit::AtMost(color, max) => {
+                let found = pick.count(color);
+                (found > *max).then(|| (Some(color.clone()), found))
+            },
+            Limit::TotalAtLeast(min) => {
+                let found = pick.total();
+                (found < *min).then_some((None, found))
+            },
+            Limit::TotalAtMost(max) => {
+                let found = pick.total();
+                (found > *max).then_some((None, found))
+            },
+            Limit::OnlyColors(colors) => {
+                pick.colors()
+                    .find(|color| !colors.contains(color))
+                    .map(|color| (Some(color.clone()), pick.count(color)))
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Violation {
+    pub pick: usize,
+    pub limit: Limit,
+    pub color: Option<Color>,
+    pub found: i32,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct BagConstraint {
+    pub limits: Vec<Limit>,
+}
+
+impl BagConstraint {
+    // colours that are not in the bag can never be drawn. the bag's colours
+    // are checked in the order of the default palette, red, green and then
+    // blue, so the reported colour does not depend on how colours sort
+    pub fn from_bag(bag: &CubeSet) -> Self {
+        let palette = ParseOptions::default().colors;
+        let mut colors: Vec<&Color> = bag.colors().collect();
+        colors.sort_by_key(|color| palette.iter().position(|known| known == *color).unwrap_or(palette.len()));
+        let mut limits: Vec<Limit> = colors.into_iter()
+            .map(|color| Limit::AtMost(color.clone(), bag.count(color)))
+            .collect();
+        limits.push(Limit::OnlyColors(bag.colors().cloned().collect()));
+        BagConstraint { limits }
+    }
+
+    fn check_pick(&self, pick: usize, cube_set: &CubeSet) -> Option<Violation> {
+        self.limits.iter()
+            .find_map(|limit| {
+                let (color, found) = limit.check(cube_set)?;
+                Some(Violation { pick, limit: limit.clone(), color, found })
+            })
+    }
+
+    pub fn first_violation(&self, game: &Game) -> Option<Violation> {
+        game.picks.iter()
+            .enumerate()
+            .find_map(|(pick, cube_set)| self.check_pick(pick, cube_set))
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct QueryResult<'a> {
+    pub possible: Vec<&'a Game>,
+    pub impossible: Vec<(&'a Game, Violation)>,
+}
+
+#[allow(unused)]
+pub fn query_games<'a>(games: &'a [Game], constraint: &BagConstraint) -> QueryResult<'a> {
+    let mut result = QueryResult::default();
+    for game in games {
+        match constraint.first_violation(game) {
+            Some(violation) => result.impossible.push((game, violation)),
+            None => result.possible.push(game),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::common::{UnknownColors, get_games};
+
+    fn possible_ids(result: &QueryResult) -> Vec<i32> {
+        result.possible.iter()
+            .map(|game| game.id.0)
+            .collect()
+    }
+
+    #[test]
+    fn test_testcase() {
+        let games = get_games("input/day_02/easy_test.txt").unwrap();
+        let constraint = BagConstraint::from_bag(&CubeSet::rgb(12, 13, 14));
+        let result = query_games(&games, &constraint);
+
+        assert_eq!(vec![1, 2, 5], possible_ids(&result));
+        let violations: Vec<(i32, Violation)> = result.impossible.into_iter()
+            .map(|(game, violation)| (game.id.0, violation))
+            .collect();
+        assert_eq!(vec![
+            (3, Violation { pick: 0, limit: Limit::AtMost("red".into(), 12), color: Some("red".into()), found: 20 }),
+            (4, Violation { pick: 2, limit: Limit::AtMost("red".into(), 12), color: Some("red".into()), found: 14 }),
+        ], violations);
+    }
+
+    #[test]
+    fn test_total_limits() {
+        let games = get_games("input/day_02/easy_test.txt").unwrap();
+        let constraint = BagConstraint { limits: vec![Limit::TotalAtMost(10)] };
+        let result = query_games(&games, &constraint);
+        assert_eq!(vec![1, 2, 5], possible_ids(&result));
+
+        let (_, violation) = &result.impossible[0];
+        assert_eq!(&Violation { pick: 0, limit: Limit::TotalAtMost(10), color: None, found: 34 }, violation);
+    }
+
+    #[test]
+    fn test_minimum_limits() {
+        let games = get_games("input/day_02/easy_test.txt").unwrap();
+        let constraint = BagConstraint { limits: vec![Limit::AtLeast("green".into(), 1)] };
+        let result = query_games(&games, &constraint);
+        assert_eq!(vec![2, 3, 4, 5], possible_ids(&result));
+    }
+
+    #[test]
+    fn test_color_not_in_bag() {
+        let options = ParseOptions { unknown_colors: UnknownColors::Accept, ..Default::default() };
+        let game = Game::parse_with("Game 7: 1 red; 2 blue, 1 purple", &options).unwrap();
+        let constraint = BagConstraint::from_bag(&CubeSet::rgb(12, 13, 14));
+        let violation = constraint.first_violation(&game).unwrap();
+        assert_eq!(1, violation.pick);
+        assert_eq!(Some("purple".into()), violation.color);
+        assert_eq!(1, violation.found);
+    }
+}
@@ -1,39 +1,18 @@
-use std::str::FromStr;
+use crate::common::error::AocError;
 
-use crate::common::{error::AocError, functions::file_to_lines};
+use super::common::{CubeSet, get_games};
+use super::constraint::BagConstraint;
 
-use super::common::{CubeSet, Game};
 
+pub fn sum_possible_game_ids(filename: &str) -> Result<i32, AocError> {
+    let max_allowed = CubeSet::rgb(12, 13, 14);
+    let constraint = BagConstraint::from_bag(&max_allowed);
 
-fn is_valid(cube_set: &CubeSet, truth: &CubeSet) -> bool {
-    cube_set.red <= truth.red
-    && cube_set.green <= truth.green
-    && cube_set.blue <= truth.blue
-}
-
-fn is_game_valid(game: &Game, truth: &CubeSet) -> bool {
-    let comp = |cube_set| is_valid(cube_set, truth); 
-    game.picks.iter().all(comp)
-}
-
-fn sum_possible_game_ids(filename: &str) -> Result<i32, AocError> {
-    let max_allowed = CubeSet {
-        red: 12,
-        green: 13,
-        blue: 14,
-    };
-
-    let games: Result<Vec<Game>, AocError> = file_to_lines(filename)?
-        .map(|s| s.map_err(|_| AocError::FileNotFound))
-        .map(|s| Game::from_str(s?.as_str()))
-        .collect();
-
-    let id_sum = games?.into_iter()
-        .filter(|game| is_game_valid(game, &max_allowed))
+    let id_sum = get_games(filename)?.into_iter()
+        .filter(|game| constraint.first_violation(game).is_none())
         .map(|game| game.id.0)
         .sum();
 
-    // println!("games: {games:?}");
     Ok(id_sum)
 }
 
@@ -47,7 +26,10 @@ mod test {
     use rstest::rstest;
 
     use super::*;
-    use super::super::common::GameId;
+    use std::str::FromStr;
+
+    use crate::common::parsing::ParseMode;
+    use super::super::common::{Game, GameId, ParseOptions, UnknownColors, get_games_with};
 
     #[test]
     fn test_testcase() {
@@ -63,39 +45,78 @@ mod test {
     #[case(
         "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green", 
         Game { id: GameId(1), picks: vec![
-            CubeSet { red: 4, green: 0, blue: 3 }, 
-            CubeSet { red: 1, green: 2, blue: 6 }, 
-            CubeSet { red: 0, green: 2, blue: 0 }]
+            CubeSet::rgb(4, 0, 3), 
+            CubeSet::rgb(1, 2, 6), 
+            CubeSet::rgb(0, 2, 0)]
         })]
     #[case(
         "Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue", 
         Game { id: GameId(2), picks: vec![
-            CubeSet { red: 0, green: 2, blue: 1 }, 
-            CubeSet { red: 1, green: 3, blue: 4 }, 
-            CubeSet { red: 0, green: 1, blue: 1 }]
+            CubeSet::rgb(0, 2, 1), 
+            CubeSet::rgb(1, 3, 4), 
+            CubeSet::rgb(0, 1, 1)]
         })]
     #[case(
         "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red", 
         Game { id: GameId(3), picks: vec![
-            CubeSet { red: 20, green: 8, blue: 6 }, 
-            CubeSet { red: 4, green: 13, blue: 5 }, 
-            CubeSet { red: 1, green: 5, blue: 0 }]
+            CubeSet::rgb(20, 8, 6), 
+            CubeSet::rgb(4, 13, 5), 
+            CubeSet::rgb(1, 5, 0)]
         })]
     #[case(
         "Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red", 
         Game { id: GameId(4), picks: vec![
-            CubeSet { red: 3, green: 1, blue: 6 }, 
-            CubeSet { red: 6, green: 3, blue: 0 }, 
-            CubeSet { red: 14, green: 3, blue: 15 }]
+            CubeSet::rgb(3, 1, 6), 
+            CubeSet::rgb(6, 3, 0), 
+            CubeSet::rgb(14, 3, 15)]
         })]
     #[case(
         "Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green", 
         Game { id: GameId(5), picks: vec![
-            CubeSet { red: 6, green: 3, blue: 1 }, 
-            CubeSet { red: 1, green: 2, blue: 2 }]
+            CubeSet::rgb(6, 3, 1), 
+            CubeSet::rgb(1, 2, 2)]
         })]
     fn test_cases(#[case] input: &str, #[case] expected: Game) {
         assert_eq!(Ok(expected), Game::from_str(input));
     }
 
+    #[test]
+    fn test_unknown_color() {
+        let line = "Game 6: 1 red; 2 purple, 1 blue";
+        let expected = AocError::UnknownColor { color: "purple".into(), context: line.into() };
+        assert_eq!(Err(expected), Game::from_str(line));
+
+        let options = ParseOptions { unknown_colors: UnknownColors::Accept, ..Default::default() };
+        let game = Game::parse_with(line, &options).unwrap();
+        assert_eq!(2, game.picks[1].count(&"purple".into()));
+    }
+
+    #[rstest]
+    #[case("Game 1: 3 red, 4 red", AocError::at(1, 16, "duplicate colour `red`"))]
+    #[case("Game 1: 3 red; -4 blue", AocError::at(1, 16, "negative count `-4`"))]
+    #[case("Game 1: 3 red; ; 2 blue", AocError::at(1, 16, "empty pick"))]
+    #[case("Round 1: 3 red", AocError::at(1, 1, "expected `Game <id>`, found `Round 1`"))]
+    #[case("Game x: 3 red", AocError::at(1, 6, "invalid game id `x`"))]
+    #[case("Game 1: 3 red, blue", AocError::at(1, 16, "expected `<count> <colour>`, found `blue`"))]
+    #[case("Game 1 3 red", AocError::at(1, 1, "expected `Game <id>: <picks>`"))]
+    fn test_strict_errors(#[case] input: &str, #[case] expected: AocError) {
+        assert_eq!(Err(expected), Game::from_str(input));
+    }
+
+    #[test]
+    fn test_lenient_sums_duplicates() {
+        let options = ParseOptions { mode: ParseMode::Lenient, ..Default::default() };
+        let game = Game::parse_with("Game 1: 3 red, 4 red, 1 blue", &options).unwrap();
+        assert_eq!(vec![CubeSet::rgb(7, 0, 1)], game.picks);
+    }
+
+    #[test]
+    fn test_duplicate_ids() {
+        let filename = "input/day_02/duplicate_ids_test.txt";
+        let expected = AocError::at(3, 6, "duplicate game id 2");
+        assert_eq!(Err(expected), get_games(filename));
+
+        let options = ParseOptions { mode: ParseMode::Lenient, ..Default::default() };
+        assert_eq!(3, get_games_with(filename, &options).unwrap().len());
+    }
 }
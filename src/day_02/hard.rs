@@ -1,19 +1,16 @@
-use std::str::FromStr;
+use crate::common::error::AocError;
 
-use crate::common::{error::AocError, functions::file_to_lines};
+use super::common::{Color, CubeSet, Game, ParseOptions, combine_cube_sets, get_games_with};
 
-use super::common::{CubeSet, Game};
-
-fn power(cube_set: CubeSet) -> i32 {
-    cube_set.red * cube_set.green * cube_set.blue
-}
-
-fn combine_cube_sets(a: CubeSet, b: CubeSet) -> CubeSet {
-    CubeSet { 
-        red: i32::max(a.red, b.red), 
-        green: i32::max(a.green, b.green), 
-        blue: i32::max(a.blue, b.blue), 
-    }
+// colours that never appear in the set count as zero, so the power over a
+// palette that includes them is zero as well
+fn power(cube_set: CubeSet, colors: &[Color]) -> i32 {
+    let mut all_colors: Vec<&Color> = colors.iter().chain(cube_set.colors()).collect();
+    all_colors.sort();
+    all_colors.dedup();
+    all_colors.into_iter()
+        .map(|color| cube_set.count(color))
+        .product()
 }
 
 fn get_max_cube_set(game: Game) -> Option<CubeSet> {
@@ -21,20 +18,19 @@ fn get_max_cube_set(game: Game) -> Option<CubeSet> {
         .reduce(combine_cube_sets)
 }
 
-fn sum_game_powers(filename: &str) -> Result<i32, AocError> {
-    let games: Result<Vec<Game>, AocError> = file_to_lines(filename)?
-        .map(|s| s.map_err(|_| AocError::FileNotFound))
-        .map(|s| Game::from_str(s?.as_str()))
-        .collect();
-
-    let id_sum = games?.into_iter()
+fn sum_game_powers_with(filename: &str, options: &ParseOptions) -> Result<i32, AocError> {
+    let id_sum = get_games_with(filename, options)?.into_iter()
         .filter_map(get_max_cube_set)
-        .map(power)
+        .map(|cube_set| power(cube_set, &options.colors))
         .sum();
 
     Ok(id_sum)
 }
 
+pub fn sum_game_powers(filename: &str) -> Result<i32, AocError> {
+    sum_game_powers_with(filename, &ParseOptions::default())
+}
+
 #[allow(unused)]
 pub fn solve() -> Result<i32, AocError> {
     sum_game_powers("input/day_02/puzzle.txt")
@@ -54,4 +50,22 @@ mod test {
     fn test_solve() {
         assert_eq!(Ok(66016), solve());
     }
+
+    #[test]
+    fn test_extra_colors() {
+        let options = ParseOptions {
+            colors: vec!["red".into(), "green".into(), "blue".into(), "yellow".into()],
+            ..Default::default()
+        };
+        let game = Game::parse_with("Game 1: 3 blue, 4 red, 2 yellow; 1 red, 2 green, 6 blue", &options).unwrap();
+        let max = get_max_cube_set(game).unwrap();
+        assert_eq!(4 * 2 * 6 * 2, power(max, &options.colors));
+    }
+
+    #[test]
+    fn test_missing_color_in_palette() {
+        let max = combine_cube_sets(CubeSet::rgb(4, 0, 3), CubeSet::rgb(1, 0, 6));
+        assert_eq!(CubeSet::rgb(4, 0, 6), max);
+        assert_eq!(0, power(max, &ParseOptions::default().colors));
+    }
 }
@@ -0,0 +1,117 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::common::error::AocError;
+
+use super::common::{Schematic, NumberSpan, SymbolPosition, locate_numbers, locate_symbols, get_schematic};
+
+#[derive(Debug, PartialEq)]
+pub struct SharedNumber {
+    pub number: NumberSpan,
+    pub symbols: Vec<SymbolPosition>,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct SchematicStatistics {
+    pub symbol_counts: BTreeMap<char, usize>,
+    pub multi_symbol_numbers: Vec<SharedNumber>,
+    pub unattached_numbers: Vec<NumberSpan>,
+    pub isolated_symbols: Vec<SymbolPosition>,
+    pub duplicate_values: BTreeMap<i32, usize>,
+}
+
+fn count_symbols(symbols: &[SymbolPosition]) -> BTreeMap<char, usize> {
+    symbols.iter().fold(BTreeMap::new(), |mut counts, position| {
+        *counts.entry(position.symbol).or_insert(0) += 1;
+        counts
+    })
+}
+
+fn count_duplicates(numbers: &[NumberSpan]) -> BTreeMap<i32, usize> {
+    let mut counts = numbers.iter().fold(BTreeMap::new(), |mut counts, span| {
+        *counts.entry(span.number).or_insert(0) += 1;
+        counts
+    });
+    counts.retain(|_, count| *count > 1);
+    counts
+}
+
+fn touching_symbols(span: &NumberSpan, symbols: &[SymbolPosition]) -> Vec<SymbolPosition> {
+    symbols.iter()
+        .filter(|symbol| span.touches(symbol.row, symbol.col))
+        .copied()
+        .collect()
+}
+
+pub fn analyze_schematic(schematic: &Schematic) -> SchematicStatistics {
+    let numbers = locate_numbers(schematic);
+    let symbols = locate_symbols(schematic);
+
+    let mut statistics = SchematicStatistics {
+        symbol_counts: count_symbols(&symbols),
+        duplicate_values: count_duplicates(&numbers),
+        ..Default::default()
+    };
+
+    let mut touched = BTreeSet::new();
+    for span in numbers {
+        let neighbors = touching_symbols(&span, &symbols);
+        touched.extend(neighbors.iter().copied());
+        match neighbors.len() {
+            0 => statistics.unattached_numbers.push(span),
+            1 => {},
+            _ => statistics.multi_symbol_numbers.push(SharedNumber { number: span, symbols: neighbors }),
+        }
+    }
+
+    statistics.isolated_symbols = symbols.into_iter()
+        .filter(|symbol| !touched.contains(symbol))
+        .collect();
+    statistics
+}
+
+#[allow(unused)]
+pub fn analyze_file(filename: &str) -> Result<SchematicStatistics, AocError> {
+    let schematic = get_schematic(filename)?;
+    Ok(analyze_schematic(&schematic))
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn test_testcase() {
+        let statistics = analyze_file("input/day_03/easy_test.txt").unwrap();
+        let expected_counts = BTreeMap::from([('#', 1), ('$', 1), ('*', 3), ('+', 1)]);
+        assert_eq!(expected_counts, statistics.symbol_counts);
+
+        let unattached: Vec<i32> = statistics.unattached_numbers.iter()
+            .map(|span| span.number)
+            .collect();
+        assert_eq!(vec![114, 58], unattached);
+        assert!(statistics.multi_symbol_numbers.is_empty());
+        assert!(statistics.isolated_symbols.is_empty());
+        assert!(statistics.duplicate_values.is_empty());
+    }
+
+    #[test]
+    fn test_manual() {
+        let input = "12.#\n..*.\n.12.\n.$..\n";
+        let schematic = Schematic::from_str(input).unwrap();
+        let statistics = analyze_schematic(&schematic);
+
+        assert_eq!(BTreeMap::from([(12, 2)]), statistics.duplicate_values);
+        assert_eq!(vec![SymbolPosition { row: 0, col: 3, symbol: '#' }], statistics.isolated_symbols);
+        assert!(statistics.unattached_numbers.is_empty());
+        assert_eq!(1, statistics.multi_symbol_numbers.len());
+
+        let shared = &statistics.multi_symbol_numbers[0];
+        assert_eq!(2, shared.number.row);
+        assert_eq!(vec![
+            SymbolPosition { row: 1, col: 2, symbol: '*' },
+            SymbolPosition { row: 3, col: 1, symbol: '$' },
+        ], shared.symbols);
+    }
+}
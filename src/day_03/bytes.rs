@@ -0,0 +1,111 @@
+use crate::common::{bytes::{lines, parse_u64}, error::AocError};
+
+// the rows borrow from the input, the only allocation is the row index itself
+struct Grid<'a> {
+    rows: Vec<&'a [u8]>,
+}
+
+fn is_symbol(b: u8) -> bool {
+    b != b'.' && !b.is_ascii_digit()
+}
+
+impl<'a> Grid<'a> {
+    fn new(input: &'a [u8]) -> Self {
+        Grid { rows: lines(input).collect() }
+    }
+
+    fn get(&self, row: usize, col: usize) -> u8 {
+        self.rows.get(row).and_then(|r| r.get(col)).copied().unwrap_or(b'.')
+    }
+
+    // rows and columns around `row` and `start..end`, clamped at the top and left
+    fn touches_symbol(&self, row: usize, start: usize, end: usize) -> bool {
+        (row.saturating_sub(1)..=row + 1)
+            .any(|r| (start.saturating_sub(1)..=end).any(|c| is_symbol(self.get(r, c))))
+    }
+
+    // each number of a row as (start, end, value)
+    fn numbers(&self, row: usize) -> impl Iterator<Item = Result<(usize, usize, u64), AocError>> + '_ {
+        let line = self.rows[row];
+        (0..line.len())
+            .filter(move |col| line[*col].is_ascii_digit() && (*col == 0 || !line[col - 1].is_ascii_digit()))
+            .map(move |start| {
+                let end = start + line[start..].iter().take_while(|b| b.is_ascii_digit()).count();
+                Ok((start, end, parse_u64(&line[start..end])?))
+            })
+    }
+
+    // the number whose digits include `col`
+    fn number_at(&self, row: usize, col: usize) -> Option<Result<(usize, u64), AocError>> {
+        let line = self.rows.get(row)?;
+        if !line.get(col)?.is_ascii_digit() {
+            return None;
+        }
+        let start = col - line[..col].iter().rev().take_while(|b| b.is_ascii_digit()).count();
+        let end = col + line[col..].iter().take_while(|b| b.is_ascii_digit()).count();
+        Some(parse_u64(&line[start..end]).map(|value| (start, value)))
+    }
+
+    fn gear_ratio(&self, row: usize, col: usize) -> Result<Option<u64>, AocError> {
+        // at most two numbers per neighbouring row, as digits left and right
+        // of a digit belong to the same number
+        let mut found: [(usize, usize, u64); 6] = [(0, 0, 0); 6];
+        let mut count = 0;
+        for r in row.saturating_sub(1)..=row + 1 {
+            for c in col.saturating_sub(1)..=col + 1 {
+                if let Some(number) = self.number_at(r, c) {
+                    let (start, value) = number?;
+                    if !found[..count].iter().any(|(fr, fs, _)| (*fr, *fs) == (r, start)) {
+                        found[count] = (r, start, value);
+                        count += 1;
+                    }
+                }
+            }
+        }
+        Ok((count == 2).then(|| found[0].2 * found[1].2))
+    }
+}
+
+pub fn solve_easy(input: &[u8]) -> Result<u64, AocError> {
+    let grid = Grid::new(input);
+    let mut sum = 0;
+    for row in 0..grid.rows.len() {
+        for number in grid.numbers(row) {
+            let (start, end, value) = number?;
+            if grid.touches_symbol(row, start, end) {
+                sum += value;
+            }
+        }
+    }
+    Ok(sum)
+}
+
+pub fn solve_hard(input: &[u8]) -> Result<u64, AocError> {
+    let grid = Grid::new(input);
+    let mut sum = 0;
+    for (row, line) in grid.rows.iter().enumerate() {
+        for col in (0..line.len()).filter(|col| line[*col] == b'*') {
+            sum += grid.gear_ratio(row, col)?.unwrap_or(0);
+        }
+    }
+    Ok(sum)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_testcase() {
+        let input = std::fs::read("input/day_03/easy_test.txt").unwrap();
+        assert_eq!(Ok(4361), solve_easy(&input));
+        assert_eq!(Ok(467835), solve_hard(&input));
+    }
+
+    #[test]
+    fn test_solve() {
+        let input = std::fs::read("input/day_03/puzzle.txt").unwrap();
+        assert_eq!(Ok(517021), solve_easy(&input));
+        assert_eq!(Ok(81296995), solve_hard(&input));
+    }
+}
@@ -1,4 +1,4 @@
-use std::str::FromStr;
+use std::{str::FromStr, ops::AddAssign};
 
 use crate::common::{error::AocError, traits::ToBaseTen};
 
@@ -21,7 +21,7 @@ pub enum Token {
     Empty,
 }
 
-fn line_to_token_vec(line: &str) -> Vec<Token> {
+pub fn line_to_token_vec(line: &str) -> Vec<Token> {
     line.chars()
         .map(|ch| ch.into())
         .collect()
@@ -77,7 +77,7 @@ impl From<char> for Token {
 fn get_neighbors(schematic: &Schematic, row: i32, col: i32) -> Vec<&Token> {
     let mut neighbors = vec![];
     let m = schematic.tokens.len() as i32;
-    let n = schematic.tokens.get(0).map(|v| v.len()).unwrap_or(0) as i32;
+    let n = schematic.tokens.first().map(|v| v.len()).unwrap_or(0) as i32;
 
     for x in -1..=1 {
         for y in -1..=1 {
@@ -143,3 +143,99 @@ pub fn get_schematic(filename: &str) -> Result<Schematic, AocError> {
     Schematic::from_str(input.as_str())
 }
 
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NumberSpan {
+    pub number: i32,
+    pub row: usize,
+    pub col_start: usize,
+    pub col_end: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SymbolPosition {
+    pub row: usize,
+    pub col: usize,
+    pub symbol: char,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct SchematicSums {
+    pub part_number_sum: i32,
+    pub gear_ratio_sum: i32,
+}
+
+impl AddAssign for SchematicSums {
+    fn add_assign(&mut self, rhs: Self) {
+        self.part_number_sum += rhs.part_number_sum;
+        self.gear_ratio_sum += rhs.gear_ratio_sum;
+    }
+}
+
+impl Digit {
+    pub fn number(&self) -> i32 {
+        match self {
+            Digit::Unchecked { number } => *number,
+            Digit::Checked { number, .. } => *number,
+        }
+    }
+}
+
+impl NumberSpan {
+    pub fn touches(&self, row: usize, col: usize) -> bool {
+        self.row.abs_diff(row) <= 1
+            && self.col_start <= col + 1
+            && col <= self.col_end + 1
+    }
+}
+
+pub fn locate_row_numbers(tokens: &[Token], row: usize) -> Vec<NumberSpan> {
+    let mut spans: Vec<NumberSpan> = vec![];
+    let mut current: Option<NumberSpan> = None;
+
+    for (col, token) in tokens.iter().enumerate() {
+        current = match (current, token) {
+            (Some(span), Token::Number(digit)) => Some(NumberSpan {
+                number: 10 * span.number + digit.number(),
+                col_end: col,
+                ..span
+            }),
+            (None, Token::Number(digit)) => Some(NumberSpan {
+                number: digit.number(),
+                row,
+                col_start: col,
+                col_end: col,
+            }),
+            (span, Token::Symbol(_) | Token::Empty) => {
+                spans.extend(span);
+                None
+            },
+        };
+    }
+    spans.extend(current);
+    spans
+}
+
+pub fn locate_numbers(schematic: &Schematic) -> Vec<NumberSpan> {
+    schematic.tokens.iter()
+        .enumerate()
+        .flat_map(|(row, tokens)| locate_row_numbers(tokens, row))
+        .collect()
+}
+
+pub fn locate_row_symbols(tokens: &[Token], row: usize) -> Vec<SymbolPosition> {
+    tokens.iter()
+        .enumerate()
+        .filter_map(|(col, token)| match token {
+            Token::Symbol(symbol) => Some(SymbolPosition { row, col, symbol: *symbol }),
+            Token::Number(_) | Token::Empty => None,
+        })
+        .collect()
+}
+
+pub fn locate_symbols(schematic: &Schematic) -> Vec<SymbolPosition> {
+    schematic.tokens.iter()
+        .enumerate()
+        .flat_map(|(row, tokens)| locate_row_symbols(tokens, row))
+        .collect()
+}
@@ -70,7 +70,7 @@ fn get_schematic_sum(schematic: Schematic) -> i32 {
     numbers.into_iter().sum()
 }
 
-fn solve_file(filename: &str) -> Result<i32, AocError> {
+pub fn solve_file(filename: &str) -> Result<i32, AocError> {
     let schematic = get_schematic(filename)?;
     Ok(get_schematic_sum(schematic))
 }
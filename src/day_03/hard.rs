@@ -163,7 +163,7 @@ fn get_schematic_cog_sum(schematic: Schematic) -> i32 {
     check_cogs(combined_tokens.as_slice())
 }
 
-fn solve_file(filename: &str) -> Result<i32, AocError> {
+pub fn solve_file(filename: &str) -> Result<i32, AocError> {
     let schematic = get_schematic(filename)?;
     Ok(get_schematic_cog_sum(schematic))
 }
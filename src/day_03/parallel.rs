@@ -0,0 +1,113 @@
+use std::ops::Range;
+
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+use crate::common::error::AocError;
+
+use super::common::{Schematic, SchematicSums, NumberSpan, SymbolPosition, get_schematic, locate_row_numbers, locate_row_symbols};
+
+fn to_bands(num_rows: usize, band_height: usize) -> Vec<Range<usize>> {
+    let band_height = usize::max(band_height, 1);
+    (0..num_rows).step_by(band_height)
+        .map(|start| start..usize::min(start + band_height, num_rows))
+        .collect()
+}
+
+// a band owns the rows in `owned`, and reads one extra row above and below
+fn with_overlap(owned: &Range<usize>, num_rows: usize) -> Range<usize> {
+    owned.start.saturating_sub(1)..usize::min(owned.end + 1, num_rows)
+}
+
+fn gear_ratio(gear: &SymbolPosition, numbers: &[NumberSpan]) -> Option<i32> {
+    let neighbors: Vec<&NumberSpan> = numbers.iter()
+        .filter(|span| span.touches(gear.row, gear.col))
+        .collect();
+
+    match neighbors.as_slice() {
+        [first, second] => Some(first.number * second.number),
+        _ => None,
+    }
+}
+
+fn sum_band(schematic: &Schematic, owned: Range<usize>) -> SchematicSums {
+    let context = with_overlap(&owned, schematic.tokens.len());
+    let rows = &schematic.tokens[context.clone()];
+    let numbers: Vec<NumberSpan> = rows.iter()
+        .zip(context.clone())
+        .flat_map(|(tokens, row)| locate_row_numbers(tokens, row))
+        .collect();
+    let symbols: Vec<SymbolPosition> = rows.iter()
+        .zip(context)
+        .flat_map(|(tokens, row)| locate_row_symbols(tokens, row))
+        .collect();
+
+    let part_number_sum = numbers.iter()
+        .filter(|span| owned.contains(&span.row))
+        .filter(|span| symbols.iter().any(|symbol| span.touches(symbol.row, symbol.col)))
+        .map(|span| span.number)
+        .sum();
+
+    let gear_ratio_sum = symbols.iter()
+        .filter(|symbol| symbol.symbol == '*' && owned.contains(&symbol.row))
+        .filter_map(|gear| gear_ratio(gear, &numbers))
+        .sum();
+
+    SchematicSums { part_number_sum, gear_ratio_sum }
+}
+
+pub fn sum_schematic_parallel(schematic: &Schematic, band_height: usize) -> SchematicSums {
+    to_bands(schematic.tokens.len(), band_height)
+        .into_par_iter()
+        .map(|owned| sum_band(schematic, owned))
+        .reduce(SchematicSums::default, |mut total, sums| {
+            total += sums;
+            total
+        })
+}
+
+fn default_band_height(num_rows: usize) -> usize {
+    num_rows.div_ceil(rayon::current_num_threads())
+}
+
+pub fn solve_file(filename: &str) -> Result<SchematicSums, AocError> {
+    let schematic = get_schematic(filename)?;
+    let band_height = default_band_height(schematic.tokens.len());
+    Ok(sum_schematic_parallel(&schematic, band_height))
+}
+
+#[allow(unused)]
+pub fn solve() -> Result<SchematicSums, AocError> {
+    solve_file("input/day_03/puzzle.txt")
+}
+
+#[cfg(test)]
+mod test {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[test]
+    fn test_testcase() {
+        let expected = SchematicSums { part_number_sum: 4361, gear_ratio_sum: 467835 };
+        assert_eq!(Ok(expected), solve_file("input/day_03/easy_test.txt"));
+    }
+
+    #[test]
+    fn test_solve() {
+        let expected = SchematicSums { part_number_sum: 517021, gear_ratio_sum: 81296995 };
+        assert_eq!(Ok(expected), solve());
+    }
+
+    #[rstest]
+    #[case(0)]
+    #[case(1)]
+    #[case(2)]
+    #[case(3)]
+    #[case(7)]
+    #[case(1000)]
+    fn test_band_heights(#[case] band_height: usize) {
+        let schematic = get_schematic("input/day_03/puzzle.txt").unwrap();
+        let expected = SchematicSums { part_number_sum: 517021, gear_ratio_sum: 81296995 };
+        assert_eq!(expected, sum_schematic_parallel(&schematic, band_height));
+    }
+}
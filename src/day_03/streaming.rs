@@ -0,0 +1,136 @@
+use std::{fs::File, io::{BufRead, BufReader}};
+
+use crate::common::{error::AocError, traits::ToAocError};
+
+use super::common::{Token, NumberSpan, SchematicSums, line_to_token_vec, locate_row_numbers};
+
+struct Row {
+    index: usize,
+    tokens: Vec<Token>,
+    numbers: Vec<NumberSpan>,
+}
+
+impl Row {
+    fn new(line: &str, index: usize) -> Self {
+        let tokens = line_to_token_vec(line);
+        let numbers = locate_row_numbers(&tokens, index);
+        Row { index, tokens, numbers }
+    }
+
+    fn has_symbol_near(&self, span: &NumberSpan) -> bool {
+        let first = span.col_start.saturating_sub(1);
+        self.tokens.iter()
+            .skip(first)
+            .take(span.col_end + 2 - first)
+            .any(|token| matches!(token, Token::Symbol(_)))
+    }
+
+    fn gears(&self) -> impl Iterator<Item = usize> + '_ {
+        self.tokens.iter()
+            .enumerate()
+            .filter(|(_, token)| **token == Token::Symbol('*'))
+            .map(|(col, _)| col)
+    }
+}
+
+// holds at most three rows, only the middle row is summed
+#[derive(Default)]
+struct Window {
+    above: Option<Row>,
+    middle: Option<Row>,
+    below: Option<Row>,
+}
+
+impl Window {
+    fn rows(&self) -> impl Iterator<Item = &Row> {
+        [&self.above, &self.middle, &self.below].into_iter().flatten()
+    }
+
+    fn gear_ratio(&self, row: usize, col: usize) -> Option<i32> {
+        let neighbors: Vec<i32> = self.rows()
+            .flat_map(|r| r.numbers.iter())
+            .filter(|span| span.touches(row, col))
+            .map(|span| span.number)
+            .collect();
+
+        match neighbors.as_slice() {
+            [first, second] => Some(first * second),
+            _ => None,
+        }
+    }
+
+    fn sum_middle(&self, middle: &Row) -> SchematicSums {
+        let part_number_sum = middle.numbers.iter()
+            .filter(|span| self.rows().any(|r| r.has_symbol_near(span)))
+            .map(|span| span.number)
+            .sum();
+        let gear_ratio_sum = middle.gears()
+            .filter_map(|col| self.gear_ratio(middle.index, col))
+            .sum();
+        SchematicSums { part_number_sum, gear_ratio_sum }
+    }
+
+    fn push(&mut self, row: Option<Row>) -> SchematicSums {
+        self.above = self.middle.take();
+        self.middle = self.below.take();
+        self.below = row;
+        self.middle.as_ref()
+            .map(|middle| self.sum_middle(middle))
+            .unwrap_or_default()
+    }
+}
+
+pub fn solve_stream<R: BufRead>(reader: R) -> Result<SchematicSums, AocError> {
+    let mut window = Window::default();
+    let mut sums = SchematicSums::default();
+
+    for (index, line) in reader.lines().enumerate() {
+        let line = line.to_aoc_error(AocError::FileNotFound)?;
+        sums += window.push(Some(Row::new(line.as_str(), index)));
+    }
+    sums += window.push(None);
+    Ok(sums)
+}
+
+pub fn solve_file(filename: &str) -> Result<SchematicSums, AocError> {
+    let file = File::open(filename).to_aoc_error(AocError::FileNotFound)?;
+    solve_stream(BufReader::new(file))
+}
+
+#[allow(unused)]
+pub fn solve() -> Result<SchematicSums, AocError> {
+    solve_file("input/day_03/puzzle.txt")
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Cursor;
+
+    use super::*;
+
+    #[test]
+    fn test_testcase() {
+        let expected = SchematicSums { part_number_sum: 4361, gear_ratio_sum: 467835 };
+        assert_eq!(Ok(expected), solve_file("input/day_03/easy_test.txt"));
+    }
+
+    #[test]
+    fn test_solve() {
+        let expected = SchematicSums { part_number_sum: 517021, gear_ratio_sum: 81296995 };
+        assert_eq!(Ok(expected), solve());
+    }
+
+    #[test]
+    fn test_manual() {
+        let input = Cursor::new("123\n4*6\n789");
+        let expected = SchematicSums { part_number_sum: 123 + 4 + 6 + 789, gear_ratio_sum: 0 };
+        assert_eq!(Ok(expected), solve_stream(input));
+    }
+
+    #[test]
+    fn test_single_row() {
+        let input = Cursor::new("12*3.");
+        let expected = SchematicSums { part_number_sum: 15, gear_ratio_sum: 36 };
+        assert_eq!(Ok(expected), solve_stream(input));
+    }
+}
@@ -0,0 +1,163 @@
+use std::collections::HashSet;
+
+const BITS: i32 = 128;
+
+// numbers in 0..128 are stored as bits, any set containing a number outside
+// that range falls back to a HashSet
+#[derive(Debug, Clone, PartialEq)]
+pub enum NumberSet {
+    Bits([u64; 2]),
+    Hashed(HashSet<i32>),
+}
+
+impl Default for NumberSet {
+    fn default() -> Self {
+        NumberSet::Bits([0; 2])
+    }
+}
+
+fn in_range(n: i32) -> bool {
+    (0..BITS).contains(&n)
+}
+
+impl NumberSet {
+    pub fn insert(&mut self, n: i32) -> bool {
+        match self {
+            NumberSet::Bits(bits) if in_range(n) => {
+                let (word, mask) = ((n / 64) as usize, 1u64 << (n % 64));
+                let is_new = bits[word] & mask == 0;
+                bits[word] |= mask;
+                is_new
+            },
+            NumberSet::Bits(_) => {
+                let mut hashed: HashSet<i32> = self.iter().collect();
+                let is_new = hashed.insert(n);
+                *self = NumberSet::Hashed(hashed);
+                is_new
+            },
+            NumberSet::Hashed(set) => set.insert(n),
+        }
+    }
+
+    pub fn contains(&self, n: i32) -> bool {
+        match self {
+            NumberSet::Bits(bits) => in_range(n) && bits[(n / 64) as usize] & (1u64 << (n % 64)) != 0,
+            NumberSet::Hashed(set) => set.contains(&n),
+        }
+    }
+
+    #[allow(unused)]
+    pub fn len(&self) -> usize {
+        match self {
+            NumberSet::Bits(bits) => bits.iter().map(|word| word.count_ones() as usize).sum(),
+            NumberSet::Hashed(set) => set.len(),
+        }
+    }
+
+    pub fn iter(&self) -> Box<dyn Iterator<Item = i32> + '_> {
+        match self {
+            NumberSet::Bits(_) => Box::new((0..BITS).filter(|n| self.contains(*n))),
+            NumberSet::Hashed(set) => Box::new(set.iter().copied()),
+        }
+    }
+
+    pub fn intersection_len(&self, other: &NumberSet) -> usize {
+        match (self, other) {
+            (NumberSet::Bits(a), NumberSet::Bits(b)) => {
+                a.iter().zip(b)
+                    .map(|(a, b)| (a & b).count_ones() as usize)
+                    .sum()
+            },
+            (NumberSet::Hashed(a), NumberSet::Hashed(b)) => a.intersection(b).count(),
+            (NumberSet::Hashed(set), bits) | (bits, NumberSet::Hashed(set)) => {
+                set.iter().filter(|n| bits.contains(**n)).count()
+            },
+        }
+    }
+}
+
+impl FromIterator<i32> for NumberSet {
+    fn from_iter<T: IntoIterator<Item = i32>>(iter: T) -> Self {
+        let mut set = NumberSet::default();
+        for n in iter {
+            set.insert(n);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rstest::rstest;
+
+    use crate::common::bench::measure;
+
+    use super::*;
+
+    #[rstest]
+    #[case(vec![41, 48, 83, 86, 17], vec![83, 86, 6, 31, 17, 9, 48, 53], 4)]
+    #[case(vec![0, 63, 64, 127], vec![127, 64, 63, 0, 1], 4)]
+    #[case(vec![1, 200, -3], vec![200, 1, 2], 2)]
+    #[case(vec![1, 2, 3], vec![3, 1000], 1)]
+    #[case(vec![], vec![1, 2], 0)]
+    fn test_intersection(#[case] a: Vec<i32>, #[case] b: Vec<i32>, #[case] expected: usize) {
+        let hashed_a: HashSet<i32> = a.iter().copied().collect();
+        let hashed_b: HashSet<i32> = b.iter().copied().collect();
+        let a: NumberSet = a.into_iter().collect();
+        let b: NumberSet = b.into_iter().collect();
+        assert_eq!(expected, a.intersection_len(&b));
+        assert_eq!(expected, b.intersection_len(&a));
+        assert_eq!(hashed_a.intersection(&hashed_b).count(), expected);
+    }
+
+    #[test]
+    fn test_fallback() {
+        let mut set: NumberSet = [5, 9].into_iter().collect();
+        assert!(matches!(set, NumberSet::Bits(_)));
+        assert!(set.insert(128));
+        assert!(!set.insert(5));
+        assert!(matches!(set, NumberSet::Hashed(_)));
+        assert_eq!(3, set.len());
+        assert!(set.contains(9) && set.contains(128) && !set.contains(10));
+    }
+
+    // xorshift, so generated cards are reproducible without a rand dependency
+    fn generate_cards(n: usize) -> Vec<(Vec<i32>, Vec<i32>)> {
+        let mut state = 0x2545f4914f6cdd1du64;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state % 99) as i32 + 1
+        };
+        (0..n)
+            .map(|_| ((0..10).map(|_| next()).collect(), (0..25).map(|_| next()).collect()))
+            .collect()
+    }
+
+    #[test]
+    #[ignore = "benchmark, run with --release"]
+    fn bench_matching() {
+        let cards = generate_cards(2_000_000);
+        let hashed: Vec<(HashSet<i32>, HashSet<i32>)> = cards.iter()
+            .map(|(a, b)| (a.iter().copied().collect(), b.iter().copied().collect()))
+            .collect();
+        let bitsets: Vec<(NumberSet, NumberSet)> = cards.iter()
+            .map(|(a, b)| (a.iter().copied().collect(), b.iter().copied().collect()))
+            .collect();
+
+        let (hashed_sum, hashed_time) = measure("hashset intersection", || {
+            hashed.iter()
+                .map(|(a, b)| a.intersection(b).collect::<HashSet<&i32>>().len())
+                .sum::<usize>()
+        });
+        let (bitset_sum, bitset_time) = measure("bitset popcount", || {
+            bitsets.iter()
+                .map(|(a, b)| a.intersection_len(b))
+                .sum::<usize>()
+        });
+
+        assert_eq!(hashed_sum, bitset_sum);
+        println!("speedup: {:.1}x", hashed_time.as_secs_f64() / bitset_time.as_secs_f64());
+    }
+}
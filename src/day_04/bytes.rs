@@ -0,0 +1,94 @@
+use crate::common::{bytes::{lines, numbers}, error::AocError};
+
+use super::bitset::NumberSet;
+
+// card numbers are below 128 in every real input, so a card's winning numbers
+// usually fit in one u128, larger numbers fall back to a `NumberSet` like the
+// idiomatic solver uses
+fn card_matches(line: &[u8], line_number: usize) -> Result<u32, AocError> {
+    let invalid = |reason: &str| AocError::at(line_number, 1, reason);
+    let colon = line.iter().position(|b| *b == b':').ok_or_else(|| invalid("expected `Card <id>: <numbers>`"))?;
+    let bar = line.iter().position(|b| *b == b'|').ok_or_else(|| invalid("expected `<winning> | <picked>`"))?;
+    if bar < colon {
+        return Err(invalid("expected `<winning> | <picked>` after the colon"));
+    }
+    let (winning, picked) = (&line[colon + 1..bar], &line[bar + 1..]);
+    let to_bits = |numbers_bytes: &[u8]| {
+        numbers(numbers_bytes).try_fold(Some(0u128), |bits, number| match number? {
+            n if n < 128 => Ok(bits.map(|bits| bits | 1 << n)),
+            _ => Ok(None),
+        })
+    };
+    let to_set = |numbers_bytes: &[u8]| {
+        numbers(numbers_bytes)
+            .map(|number| i32::try_from(number?).map_err(|_| invalid("number out of range")))
+            .collect::<Result<NumberSet, _>>()
+    };
+    match (to_bits(winning)?, to_bits(picked)?) {
+        (Some(winning), Some(picked)) => Ok((winning & picked).count_ones()),
+        _ => Ok(to_set(winning)?.intersection_len(&to_set(picked)?) as u32),
+    }
+}
+
+pub fn solve_easy(input: &[u8]) -> Result<u64, AocError> {
+    lines(input).enumerate().try_fold(0u64, |sum, (i, line)| {
+        let score = match card_matches(line, i + 1)? {
+            0 => 0,
+            n => 1u64.checked_shl(n - 1).ok_or(AocError::Overflow)?,
+        };
+        sum.checked_add(score).ok_or(AocError::Overflow)
+    })
+}
+
+pub fn solve_hard(input: &[u8]) -> Result<u64, AocError> {
+    let matches = lines(input).enumerate()
+        .map(|(i, line)| card_matches(line, i + 1))
+        .collect::<Result<Vec<u32>, _>>()?;
+    let mut copies = vec![1u64; matches.len()];
+    for (i, n) in matches.iter().enumerate() {
+        let n_copy = copies[i];
+        for item in copies.iter_mut().skip(i + 1).take(*n as usize) {
+            *item = item.checked_add(n_copy).ok_or(AocError::Overflow)?;
+        }
+    }
+    copies.into_iter()
+        .try_fold(0u64, |total, item| total.checked_add(item).ok_or(AocError::Overflow))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_testcase() {
+        let input = std::fs::read("input/day_04/easy_test.txt").unwrap();
+        assert_eq!(Ok(13), solve_easy(&input));
+        assert_eq!(Ok(30), solve_hard(&input));
+    }
+
+    #[test]
+    fn test_solve() {
+        let input = std::fs::read("input/day_04/puzzle.txt").unwrap();
+        assert_eq!(Ok(22488), solve_easy(&input));
+        assert_eq!(Ok(7013204), solve_hard(&input));
+    }
+
+    #[test]
+    fn test_large_numbers() {
+        let input = b"Card 1: 200 3 | 200 3 5\nCard 2: 1 | 2\n";
+        assert_eq!(Ok(2), solve_easy(input));
+        assert_eq!(Ok(3), solve_hard(input));
+        assert_eq!(Err(AocError::at(1, 1, "number out of range")), solve_easy(b"Card 1: 3000000000 | 3"));
+    }
+
+    #[test]
+    fn test_bar_before_colon() {
+        let expected = Err(AocError::at(2, 1, "expected `<winning> | <picked>` after the colon"));
+        assert_eq!(expected, solve_easy(b"Card 1: 1 | 1
+Card 1 | 2: 3
+"));
+        assert_eq!(expected, solve_hard(b"Card 1: 1 | 1
+Card 1 | 2: 3
+"));
+    }
+}
@@ -0,0 +1,120 @@
+use crate::common::error::AocError;
+
+use super::{common::{BingoCard, CardId, get_bingo_cards}, scoring::copies_per_card};
+
+// every copy of `card` wins one copy of each card in `produced`
+#[derive(Debug, PartialEq)]
+pub struct CardCopies {
+    pub card: CardId,
+    pub copies: u64,
+    pub produced: Vec<CardId>,
+}
+
+impl CardCopies {
+    // always `Some` for the cards of a `Cascade`, which checks it on creation
+    pub fn contribution(&self) -> Option<u64> {
+        self.copies.checked_mul(self.produced.len() as u64)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Cascade {
+    pub cards: Vec<CardCopies>,
+}
+
+impl Cascade {
+    #[allow(unused)]
+    pub fn total(&self) -> Result<u64, AocError> {
+        self.cards.iter()
+            .try_fold(0u64, |total, card| total.checked_add(card.copies).ok_or(AocError::Overflow))
+    }
+
+    #[allow(unused)]
+    pub fn copies_of(&self, card: CardId) -> Option<u64> {
+        self.cards.iter()
+            .find(|c| c.card == card)
+            .map(|c| c.copies)
+    }
+
+    // ties are broken in favour of the earliest card
+    #[allow(unused)]
+    pub fn top_contributor(&self) -> Option<&CardCopies> {
+        self.cards.iter()
+            .max_by(|a, b| a.contribution().cmp(&b.contribution()).then(b.card.cmp(&a.card)))
+    }
+}
+
+pub fn simulate_cascade(bingo_cards: &[BingoCard]) -> Result<Cascade, AocError> {
+    let copies = copies_per_card(bingo_cards)?;
+    let mut cards = vec![];
+    for (i, (bingo_card, copies)) in bingo_cards.iter().zip(copies).enumerate() {
+        let produced: Vec<CardId> = bingo_cards.iter()
+            .skip(i + 1)
+            .take(bingo_card.num_matches())
+            .map(|card| card.card_number)
+            .collect();
+        let card = CardCopies { card: bingo_card.card_number, copies, produced };
+        card.contribution().ok_or(AocError::Overflow)?;
+        cards.push(card);
+    }
+    Ok(Cascade { cards })
+}
+
+#[allow(unused)]
+pub fn solve_file(filename: &str) -> Result<Cascade, AocError> {
+    let bingo_cards = get_bingo_cards(filename)?;
+    simulate_cascade(&bingo_cards)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_testcase() {
+        let cascade = solve_file("input/day_04/easy_test.txt").unwrap();
+        assert_eq!(Ok(30), cascade.total());
+
+        let copies: Vec<u64> = cascade.cards.iter().map(|card| card.copies).collect();
+        assert_eq!(vec![1, 2, 4, 8, 14, 1], copies);
+        assert_eq!(Some(14), cascade.copies_of(CardId(5)));
+        assert_eq!(None, cascade.copies_of(CardId(7)));
+
+        let produced = &cascade.cards[0].produced;
+        assert_eq!(&vec![CardId(2), CardId(3), CardId(4), CardId(5)], produced);
+
+        let top = cascade.top_contributor().unwrap();
+        assert_eq!(CardId(3), top.card);
+        assert_eq!(Some(8), top.contribution());
+    }
+
+    #[test]
+    fn test_solve() {
+        let cascade = solve_file("input/day_04/puzzle.txt").unwrap();
+        assert_eq!(Ok(7013204), cascade.total());
+    }
+
+    #[test]
+    fn test_exponential_copies() {
+        // every card wins a copy of all following cards, so card i has 2^i copies
+        let cards: Vec<BingoCard> = (0..70)
+            .map(|i| {
+                let numbers: Vec<i32> = (1..70 - i).collect();
+                BingoCard {
+                    card_number: CardId(i + 1),
+                    winning_numbers: numbers.iter().copied().collect(),
+                    picked_numbers: numbers.iter().copied().collect(),
+                }
+            })
+            .collect();
+        assert_eq!(Err(AocError::Overflow), simulate_cascade(&cards));
+        assert_eq!(Ok(1 << 59), simulate_cascade(&cards[10..]).map(|cascade| cascade.cards[59].copies));
+    }
+
+    #[test]
+    fn test_invalid_cards() {
+        // card ids are checked while parsing, see `validate_cards`
+        let expected = AocError::at(2, 15, "duplicate winning number 13");
+        assert_eq!(Err(expected), solve_file("input/day_04/invalid_test.txt"));
+    }
+}
@@ -1,37 +1,135 @@
-use std::{collections::HashSet, str::FromStr};
+use std::str::FromStr;
 
-use crate::common::{error::AocError, functions::{file_to_lines, to_parse_error}};
+use crate::common::{error::AocError, functions::{file_to_lines, unreadable_line}, parsing::{ParseMode, Span}};
 
-pub struct CardId(i32);
+use super::bitset::NumberSet;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CardId(pub i32);
 
 pub struct BingoCard {
     pub card_number: CardId,
-    pub winning_numbers: HashSet<i32>,
-    pub picked_numbers: HashSet<i32>,
+    pub winning_numbers: NumberSet,
+    pub picked_numbers: NumberSet,
 }
 
-fn parse_header(header: &str) -> Result<CardId, AocError> {
-    header.split(' ').last()
-        .and_then(|s| i32::from_str(s).ok())
-        .map(CardId)
-        .ok_or(AocError::ParseError)
+#[derive(Debug, Clone, Copy)]
+struct Number {
+    value: i32,
+    column: usize,
 }
 
-fn parse_number_string(input: &str) -> Result<HashSet<i32>, AocError> {
-    input.split(' ')
-        .filter(|s| !s.is_empty())
-        .map(i32::from_str)
-        .map(to_parse_error)
-        .collect()
+// a card as written in the input, before duplicates are collapsed
+struct RawCard {
+    line: usize,
+    id: Number,
+    winning: Vec<Number>,
+    picked: Vec<Number>,
 }
 
-fn parse_game_info(game_info: &str) -> Result<(HashSet<i32>, HashSet<i32>), AocError> {
-    let parts: Vec<&str> = game_info.split(" | ").collect();
+fn parse_number(span: Span) -> Result<Number, AocError> {
+    Ok(Number { value: span.parse("number")?, column: span.column })
+}
+
+fn parse_header(header: Span) -> Result<Number, AocError> {
+    let parts: Vec<Span> = header.words().collect();
     match parts.as_slice() {
-        [winning, played] => {
-            Ok((parse_number_string(winning)?, parse_number_string(played)?))
-        },
-        _ => Err(AocError::ParseError)
+        [card, id] if card.text == "Card" => parse_number(*id),
+        _ => Err(header.error(format!("expected `Card <id>`, found `{}`", header.text))),
+    }
+}
+
+fn parse_number_string(input: Span) -> Result<Vec<Number>, AocError> {
+    input.words()
+        .map(parse_number)
+        .collect()
+}
+
+fn find_duplicates(numbers: &[Number], line: usize, list: &str) -> Vec<AocError> {
+    numbers.iter()
+        .enumerate()
+        .filter(|(i, number)| numbers[..*i].iter().any(|n| n.value == number.value))
+        .map(|(_, number)| {
+            let reason = format!("duplicate {list} number {}", number.value);
+            AocError::at(line, number.column, reason)
+        })
+        .collect()
+}
+
+fn find_non_positive(numbers: &[Number], line: usize) -> Vec<AocError> {
+    numbers.iter()
+        .filter(|number| number.value <= 0)
+        .map(|number| AocError::at(line, number.column, format!("non-positive number {}", number.value)))
+        .collect()
+}
+
+impl RawCard {
+    // Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+    fn parse(s: &str, line: usize) -> Result<Self, AocError> {
+        let span = Span::new(s, line);
+        let (header, game_info) = span.split_once(": ")
+            .ok_or_else(|| span.error("expected `Card <id>: <numbers>`"))?;
+        let (winning, picked) = game_info.split_once(" | ")
+            .ok_or_else(|| game_info.error("expected `<winning> | <picked>`"))?;
+        Ok(RawCard {
+            line,
+            id: parse_header(header)?,
+            winning: parse_number_string(winning)?,
+            picked: parse_number_string(picked)?,
+        })
+    }
+
+    fn issues(&self) -> Vec<AocError> {
+        let mut issues = find_duplicates(&self.winning, self.line, "winning");
+        issues.extend(find_duplicates(&self.picked, self.line, "picked"));
+        issues.extend(find_non_positive(&self.winning, self.line));
+        issues.extend(find_non_positive(&self.picked, self.line));
+        if self.id.value <= 0 {
+            issues.push(AocError::at(self.line, self.id.column, format!("non-positive card id {}", self.id.value)));
+        }
+        issues.sort_by_key(|issue| match issue {
+            AocError::Located { column, .. } => *column,
+            _ => 0,
+        });
+        issues
+    }
+
+    fn into_card(self) -> BingoCard {
+        BingoCard {
+            card_number: CardId(self.id.value),
+            winning_numbers: self.winning.into_iter().map(|n| n.value).collect(),
+            picked_numbers: self.picked.into_iter().map(|n| n.value).collect(),
+        }
+    }
+}
+
+// every card is compared against the first one for list lengths, and against
+// the previous one for consecutive ids
+fn file_issues(cards: &[RawCard]) -> Vec<AocError> {
+    let mut issues = vec![];
+    for (i, card) in cards.iter().enumerate() {
+        issues.extend(card.issues());
+        if let Some(first) = cards.first() {
+            if card.winning.len() != first.winning.len() || card.picked.len() != first.picked.len() {
+                let reason = format!(
+                    "expected {} winning and {} picked numbers, found {} and {}",
+                    first.winning.len(), first.picked.len(), card.winning.len(), card.picked.len());
+                issues.push(AocError::at(card.line, 1, reason));
+            }
+        }
+        if let Some(previous) = i.checked_sub(1).map(|j| &cards[j]) {
+            if card.id.value != previous.id.value + 1 {
+                let reason = format!("expected card {}, found card {}", previous.id.value + 1, card.id.value);
+                issues.push(AocError::at(card.line, card.id.column, reason));
+            }
+        }
+    }
+    issues
+}
+
+impl BingoCard {
+    pub fn num_matches(&self) -> usize {
+        self.winning_numbers.intersection_len(&self.picked_numbers)
     }
 }
 
@@ -39,25 +137,39 @@ impl FromStr for BingoCard {
     type Err = AocError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let parts: Vec<&str> = s.split(": ").collect();
-        match parts.as_slice() {
-            [header, game_info] => {
-                let card_number = parse_header(header)?;
-                let (winning_numbers, picked_numbers) = parse_game_info(game_info)?;
-                Ok(BingoCard { 
-                    card_number, 
-                    winning_numbers, 
-                    picked_numbers, 
-                })
-            },
-            _ => Err(AocError::ParseError)
+        let card = RawCard::parse(s, 1)?;
+        match card.issues().into_iter().next() {
+            Some(issue) => Err(issue),
+            None => Ok(card.into_card()),
         }
     }
 }
 
-pub fn get_bingo_cards(filename: &str) -> Result<Vec<BingoCard>, AocError> {
+fn get_raw_cards(filename: &str) -> Result<Vec<RawCard>, AocError> {
     file_to_lines(filename)?
-        .flatten()
-        .map(|line| BingoCard::from_str(line.as_str()))
+        .enumerate()
+        .map(|(i, line)| RawCard::parse(line.map_err(|error| unreadable_line(i + 1, error))?.as_str(), i + 1))
         .collect()
 }
+
+#[allow(unused)]
+pub fn validate_cards(filename: &str) -> Result<Vec<AocError>, AocError> {
+    let cards = get_raw_cards(filename)?;
+    Ok(file_issues(&cards))
+}
+
+// strict parsing rejects any card that `validate_cards` reports an issue
+// for, lenient parsing collapses duplicates and skips all validation
+pub fn get_bingo_cards_with(filename: &str, mode: ParseMode) -> Result<Vec<BingoCard>, AocError> {
+    let cards = get_raw_cards(filename)?;
+    if mode == ParseMode::Strict {
+        if let Some(issue) = file_issues(&cards).into_iter().next() {
+            return Err(issue);
+        }
+    }
+    Ok(cards.into_iter().map(RawCard::into_card).collect())
+}
+
+pub fn get_bingo_cards(filename: &str) -> Result<Vec<BingoCard>, AocError> {
+    get_bingo_cards_with(filename, ParseMode::Strict)
+}
@@ -1,45 +1,25 @@
 
-use std::collections::HashSet;
-
 use crate::common::error::AocError;
 
-use super::common::{BingoCard, get_bingo_cards};
-
-fn matches_to_score(n: i32) -> i32 {
-    if n > 0 {
-        2i32.pow(n as u32 - 1)        
-    } else {
-        0
-    }
-}
-
-fn get_score(bingo_card: &BingoCard) -> i32 {
-    let intersection: HashSet<&i32> = bingo_card.winning_numbers
-        .intersection(&bingo_card.picked_numbers).collect();
-    let num_winning = intersection.len() as i32;
-    matches_to_score(num_winning)
-}
-
-fn sum_total_score(bingo_cards: Vec<BingoCard>) -> i32 {
-    bingo_cards.iter()
-        .map(get_score)
-        .sum()
-}
+use super::{common::get_bingo_cards, scoring::{ScoreMode, score_cards}};
 
-fn solve_file(filename: &str) -> Result<i32, AocError> {
+pub fn solve_file(filename: &str) -> Result<u64, AocError> {
     let bingo_cards = get_bingo_cards(filename)?;
-    let total_score = sum_total_score(bingo_cards);
-    Ok(total_score)
+    let score = score_cards(&bingo_cards, ScoreMode::Checked)?;
+    u64::try_from(score).map_err(|_| AocError::Overflow)
 }
 
 #[allow(unused)]
-pub fn solve() -> Result<i32, AocError> {
+pub fn solve() -> Result<u64, AocError> {
     solve_file("input/day_04/puzzle.txt")
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::common::parsing::ParseMode;
+
+    use super::super::common::{get_bingo_cards_with, validate_cards};
 
     #[test]
     fn test_testcase() {
@@ -50,5 +30,26 @@ mod test {
     fn test_solve() {
         assert_eq!(Ok(22488), solve());
     }
+
+    #[test]
+    fn test_validation() {
+        let expected = vec![
+            AocError::at(2, 15, "duplicate winning number 13"),
+            AocError::at(3, 48, "non-positive number 0"),
+            AocError::at(4, 1, "expected 5 winning and 8 picked numbers, found 4 and 8"),
+            AocError::at(4, 6, "expected card 4, found card 5"),
+        ];
+        assert_eq!(Ok(expected), validate_cards("input/day_04/invalid_test.txt"));
+    }
+
+    #[test]
+    fn test_parse_modes() {
+        let filename = "input/day_04/invalid_test.txt";
+        let expected = AocError::at(2, 15, "duplicate winning number 13");
+        assert!(matches!(get_bingo_cards_with(filename, ParseMode::Strict), Err(e) if e == expected));
+
+        let cards = get_bingo_cards_with(filename, ParseMode::Lenient).unwrap();
+        assert_eq!(4, cards[1].winning_numbers.len());
+    }
 }
 
@@ -0,0 +1,198 @@
+use num_bigint::BigUint;
+
+use crate::common::error::AocError;
+
+use super::common::{BingoCard, get_bingo_cards};
+
+#[allow(unused)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScoreMode {
+    // u64 arithmetic, any overflow is reported as `AocError::Overflow`
+    Checked,
+    // arbitrary precision, always exact
+    Exact,
+    // all arithmetic modulo the given value
+    Modulo(u64),
+}
+
+trait Arithmetic {
+    type Value: Clone;
+
+    fn zero(&self) -> Self::Value;
+    fn one(&self) -> Self::Value;
+    fn add(&self, a: &Self::Value, b: &Self::Value) -> Result<Self::Value, AocError>;
+    fn pow2(&self, exponent: usize) -> Result<Self::Value, AocError>;
+    fn to_big(&self, value: Self::Value) -> BigUint;
+}
+
+struct Checked;
+struct Exact;
+struct Modulo(u64);
+
+impl Arithmetic for Checked {
+    type Value = u64;
+
+    fn zero(&self) -> u64 { 0 }
+    fn one(&self) -> u64 { 1 }
+
+    fn add(&self, a: &u64, b: &u64) -> Result<u64, AocError> {
+        a.checked_add(*b).ok_or(AocError::Overflow)
+    }
+
+    fn pow2(&self, exponent: usize) -> Result<u64, AocError> {
+        u32::try_from(exponent).ok()
+            .and_then(|exponent| 1u64.checked_shl(exponent))
+            .ok_or(AocError::Overflow)
+    }
+
+    fn to_big(&self, value: u64) -> BigUint {
+        BigUint::from(value)
+    }
+}
+
+impl Arithmetic for Exact {
+    type Value = BigUint;
+
+    fn zero(&self) -> BigUint { BigUint::ZERO }
+    fn one(&self) -> BigUint { BigUint::from(1u32) }
+
+    fn add(&self, a: &BigUint, b: &BigUint) -> Result<BigUint, AocError> {
+        Ok(a + b)
+    }
+
+    fn pow2(&self, exponent: usize) -> Result<BigUint, AocError> {
+        Ok(BigUint::from(1u32) << exponent)
+    }
+
+    fn to_big(&self, value: BigUint) -> BigUint {
+        value
+    }
+}
+
+impl Arithmetic for Modulo {
+    type Value = u64;
+
+    fn zero(&self) -> u64 { 0 }
+    fn one(&self) -> u64 { 1 % self.0 }
+
+    fn add(&self, a: &u64, b: &u64) -> Result<u64, AocError> {
+        Ok(((*a as u128 + *b as u128) % self.0 as u128) as u64)
+    }
+
+    fn pow2(&self, exponent: usize) -> Result<u64, AocError> {
+        let modulus = BigUint::from(self.0);
+        let result = BigUint::from(2u32).modpow(&BigUint::from(exponent), &modulus);
+        Ok(result.iter_u64_digits().next().unwrap_or(0))
+    }
+
+    fn to_big(&self, value: u64) -> BigUint {
+        BigUint::from(value)
+    }
+}
+
+fn total_score<A: Arithmetic>(arithmetic: &A, matches: &[usize]) -> Result<BigUint, AocError> {
+    let mut total = arithmetic.zero();
+    for n in matches.iter().filter(|n| **n > 0) {
+        total = arithmetic.add(&total, &arithmetic.pow2(n - 1)?)?;
+    }
+    Ok(arithmetic.to_big(total))
+}
+
+fn card_copies<A: Arithmetic>(arithmetic: &A, matches: &[usize]) -> Result<Vec<A::Value>, AocError> {
+    let mut copies = vec![arithmetic.one(); matches.len()];
+    for (i, n) in matches.iter().enumerate() {
+        let n_copy = copies[i].clone();
+        for item in copies.iter_mut().skip(i + 1).take(*n) {
+            *item = arithmetic.add(item, &n_copy)?;
+        }
+    }
+    Ok(copies)
+}
+
+fn total_copies<A: Arithmetic>(arithmetic: &A, matches: &[usize]) -> Result<BigUint, AocError> {
+    let total = card_copies(arithmetic, matches)?.iter()
+        .try_fold(arithmetic.zero(), |total, item| arithmetic.add(&total, item))?;
+    Ok(arithmetic.to_big(total))
+}
+
+fn to_matches(bingo_cards: &[BingoCard]) -> Vec<usize> {
+    bingo_cards.iter()
+        .map(BingoCard::num_matches)
+        .collect()
+}
+
+pub fn score_cards(bingo_cards: &[BingoCard], mode: ScoreMode) -> Result<BigUint, AocError> {
+    let matches = to_matches(bingo_cards);
+    match mode {
+        ScoreMode::Checked => total_score(&Checked, &matches),
+        ScoreMode::Exact => total_score(&Exact, &matches),
+        ScoreMode::Modulo(0) => Err(AocError::SolveError),
+        ScoreMode::Modulo(m) => total_score(&Modulo(m), &matches),
+    }
+}
+
+pub fn count_card_copies(bingo_cards: &[BingoCard], mode: ScoreMode) -> Result<BigUint, AocError> {
+    let matches = to_matches(bingo_cards);
+    match mode {
+        ScoreMode::Checked => total_copies(&Checked, &matches),
+        ScoreMode::Exact => total_copies(&Exact, &matches),
+        ScoreMode::Modulo(0) => Err(AocError::SolveError),
+        ScoreMode::Modulo(m) => total_copies(&Modulo(m), &matches),
+    }
+}
+
+// the copies held of each card in file order, every card's original included
+pub fn copies_per_card(bingo_cards: &[BingoCard]) -> Result<Vec<u64>, AocError> {
+    card_copies(&Checked, &to_matches(bingo_cards))
+}
+
+#[allow(unused)]
+pub fn solve_file(filename: &str, mode: ScoreMode) -> Result<(BigUint, BigUint), AocError> {
+    let bingo_cards = get_bingo_cards(filename)?;
+    Ok((score_cards(&bingo_cards, mode)?, count_card_copies(&bingo_cards, mode)?))
+}
+
+#[cfg(test)]
+mod test {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case(ScoreMode::Checked)]
+    #[case(ScoreMode::Exact)]
+    #[case(ScoreMode::Modulo(1_000_000_007))]
+    fn test_testcase(#[case] mode: ScoreMode) {
+        let expected = (BigUint::from(13u32), BigUint::from(30u32));
+        assert_eq!(Ok(expected), solve_file("input/day_04/easy_test.txt", mode));
+    }
+
+    #[test]
+    fn test_solve() {
+        let expected = (BigUint::from(22488u32), BigUint::from(7013204u32));
+        assert_eq!(Ok(expected), solve_file("input/day_04/puzzle.txt", ScoreMode::Exact));
+    }
+
+    #[test]
+    fn test_large_match_counts() {
+        let matches = vec![70, 1];
+        assert_eq!(Err(AocError::Overflow), total_score(&Checked, &matches));
+
+        let exact: BigUint = (BigUint::from(1u32) << 69) + BigUint::from(1u32);
+        assert_eq!(Ok(exact.clone()), total_score(&Exact, &matches));
+
+        let modulus = 1_000_000_007u64;
+        let expected = exact % BigUint::from(modulus);
+        assert_eq!(Ok(expected), total_score(&Modulo(modulus), &matches));
+    }
+
+    #[test]
+    fn test_exponential_copies() {
+        // every card wins a copy of all following cards, so card i has 2^i copies
+        let matches: Vec<usize> = (0..100).rev().collect();
+        let exact: BigUint = (BigUint::from(1u32) << 100) - BigUint::from(1u32);
+        assert_eq!(Err(AocError::Overflow), total_copies(&Checked, &matches));
+        assert_eq!(Ok(exact.clone()), total_copies(&Exact, &matches));
+        assert_eq!(Ok(exact % BigUint::from(97u32)), total_copies(&Modulo(97), &matches));
+    }
+}
@@ -0,0 +1,86 @@
+use crate::common::{bytes::{lines, numbers}, error::AocError};
+
+use super::{common::{Almanac, Mapping}, hard::solve_almanac};
+
+fn to_i64(number: Result<u64, AocError>) -> Result<i64, AocError> {
+    i64::try_from(number?).map_err(|_| AocError::Overflow)
+}
+
+// the maps are only told apart by their order, so their headers are skipped
+// rather than compared
+fn parse_almanac(input: &[u8]) -> Result<Almanac, AocError> {
+    let mut lines = lines(input).enumerate();
+    let (_, seeds) = lines.next().ok_or_else(|| AocError::at(1, 1, "expected `seeds:`"))?;
+    let seeds = seeds.strip_prefix(b"seeds:").ok_or_else(|| AocError::at(1, 1, "expected `seeds:`"))?;
+    let seeds = numbers(seeds).map(to_i64).collect::<Result<Vec<_>, _>>()?;
+
+    let mut maps: Vec<Vec<Mapping>> = vec![];
+    for (i, line) in lines {
+        if line.is_empty() {
+            continue;
+        }
+        if line.ends_with(b":") {
+            maps.push(vec![]);
+            continue;
+        }
+        let mut values = numbers(line).map(to_i64);
+        let mut next = || values.next().unwrap_or(Err(AocError::at(i + 1, 1, "expected `<destination> <source> <length>`")));
+        let (to, from, length) = (next()?, next()?, next()?);
+        let map = maps.last_mut().ok_or_else(|| AocError::at(i + 1, 1, "expected a map header"))?;
+        map.push(Mapping { from, to, length });
+    }
+    for map in maps.iter_mut() {
+        map.sort_by_key(|mapping| mapping.from);
+    }
+
+    let Ok([seed_to_soil, soil_to_fertilizer, fertilizer_to_water, water_to_light,
+        light_to_temperature, temperature_to_humidity, humidity_to_location]) = <[_; 7]>::try_from(maps) else {
+        return Err(AocError::at(1, 1, "expected 7 maps"));
+    };
+    Ok(Almanac {
+        seeds,
+        seed_to_soil_map: seed_to_soil,
+        soil_to_fertilizer_map: soil_to_fertilizer,
+        fertilizer_to_water_map: fertilizer_to_water,
+        water_to_light_map: water_to_light,
+        light_to_temperature_map: light_to_temperature,
+        temperature_to_humidity_map: temperature_to_humidity,
+        humidity_to_location_map: humidity_to_location,
+    })
+}
+
+pub fn solve_easy(input: &[u8]) -> Result<i64, AocError> {
+    let almanac = parse_almanac(input)?;
+    almanac.seeds.iter()
+        .map(|seed| almanac.seed_to_location(*seed))
+        .min().ok_or(AocError::ParseError)
+}
+
+pub fn solve_hard(input: &[u8]) -> Result<i64, AocError> {
+    solve_almanac(parse_almanac(input)?)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_testcase() {
+        let input = std::fs::read("input/day_05/easy_test.txt").unwrap();
+        assert_eq!(Ok(35), solve_easy(&input));
+        assert_eq!(Ok(46), solve_hard(&input));
+    }
+
+    #[test]
+    fn test_solve() {
+        let input = std::fs::read("input/day_05/puzzle.txt").unwrap();
+        assert_eq!(Ok(26273516), solve_easy(&input));
+        assert_eq!(Ok(34039469), solve_hard(&input));
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert_eq!(Err(AocError::at(3, 1, "expected a map header")), solve_easy(b"seeds: 1\n\n1 2 3\n"));
+        assert_eq!(Err(AocError::at(1, 1, "expected 7 maps")), solve_easy(b"seeds: 1\n\na map:\n1 2 3\n"));
+    }
+}
@@ -1,6 +1,6 @@
 use std::str::FromStr;
 
-use crate::common::{error::AocError, functions::to_parse_error};
+use crate::common::{error::AocError, parsing::Span};
 
 
 #[derive(Debug)]
@@ -22,12 +22,6 @@ impl Mapping {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Hash)]
-pub struct SeedRange {
-    pub from: i64,
-    pub length: i64,
-}
-
 #[derive(Debug)]
 pub struct Almanac {
     pub seeds: Vec<i64>,
@@ -71,44 +65,74 @@ impl Almanac {
     }
 }
 
-fn parse_seeds(seeds: &str) -> Result<Vec<i64>, AocError> {
-    let seeds = seeds.split(": ").last().ok_or(AocError::ParseError)?;
-    seeds.split_ascii_whitespace()
-        .map(i64::from_str)
-        .map(to_parse_error)
-        .collect()
+const MAP_NAMES: [&str; 7] = [
+    "seed-to-soil map",
+    "soil-to-fertilizer map",
+    "fertilizer-to-water map",
+    "water-to-light map",
+    "light-to-temperature map",
+    "temperature-to-humidity map",
+    "humidity-to-location map",
+];
+
+// seeds: 79 14 55 13
+fn parse_seeds(block: Span) -> Result<Vec<i64>, AocError> {
+    block.labeled("seeds:")?.list("seed")
 }
 
-fn parse_map(map: &str) -> Result<Vec<Mapping>, AocError> {
-    let res: Result<Vec<Mapping>, AocError> = map.lines().skip(1)
-        .map(Mapping::from_str)
-        .collect();
-    let mut v = res?;
-    v.sort_by(|a, b| a.from.cmp(&b.from));
-    Ok(v)
+// seed-to-soil map:
+// 50 98 2
+// 52 50 48
+fn parse_map(block: Span, name: &str) -> Result<Vec<Mapping>, AocError> {
+    let (key, body) = block.section()?;
+    if key.text != name {
+        return Err(key.error(format!("expected `{name}:`, found `{}:`", key.text)));
+    }
+    let mut mappings = body.lines()
+        .map(Mapping::parse)
+        .collect::<Result<Vec<_>, _>>()?;
+    mappings.sort_by_key(|a| a.from);
+    Ok(mappings)
 }
 
 impl FromStr for Almanac {
     type Err = AocError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let parts: Vec<&str> = s.split("\n\n").collect();
-        match parts.as_slice() {
-            [seeds, seed_to_soil, soil_to_fertilizer, fertilizer_to_water, water_to_light, 
-            light_to_temperature, temperature_to_humidity, humidity_to_location] => {
-                // println!("{parts:?}");
-                Ok(Almanac {
-                    seeds: parse_seeds(seeds)?,
-                    seed_to_soil_map: parse_map(seed_to_soil)?,
-                    soil_to_fertilizer_map: parse_map(soil_to_fertilizer)?,
-                    fertilizer_to_water_map: parse_map(fertilizer_to_water)?,
-                    water_to_light_map: parse_map(water_to_light)?,
-                    light_to_temperature_map: parse_map(light_to_temperature)?,
-                    temperature_to_humidity_map: parse_map(temperature_to_humidity)?,
-                    humidity_to_location_map: parse_map(humidity_to_location)?, 
-                })
-            },
-            _ => Err(AocError::ParseError)
+        let document = Span::document(s);
+        let blocks = document.blocks();
+        if blocks.len() != MAP_NAMES.len() + 1 {
+            let reason = format!("expected seeds and {} maps, found {} blocks", MAP_NAMES.len(), blocks.len());
+            return Err(document.error(reason));
+        }
+        let seeds = parse_seeds(blocks[0])?;
+        let maps: Vec<Vec<Mapping>> = blocks[1..].iter().zip(MAP_NAMES)
+            .map(|(block, name)| parse_map(*block, name))
+            .collect::<Result<_, _>>()?;
+        let Ok([seed_to_soil, soil_to_fertilizer, fertilizer_to_water, water_to_light,
+            light_to_temperature, temperature_to_humidity, humidity_to_location]) = <[_; 7]>::try_from(maps) else {
+            unreachable!("the number of blocks was checked above");
+        };
+        Ok(Almanac {
+            seeds,
+            seed_to_soil_map: seed_to_soil,
+            soil_to_fertilizer_map: soil_to_fertilizer,
+            fertilizer_to_water_map: fertilizer_to_water,
+            water_to_light_map: water_to_light,
+            light_to_temperature_map: light_to_temperature,
+            temperature_to_humidity_map: temperature_to_humidity,
+            humidity_to_location_map: humidity_to_location,
+        })
+    }
+}
+
+impl Mapping {
+    // <destination start> <source start> <length>
+    fn parse(line: Span) -> Result<Self, AocError> {
+        let numbers: Vec<i64> = line.list("number")?;
+        match numbers.as_slice() {
+            [to, from, length] => Ok(Mapping { from: *from, to: *to, length: *length }),
+            _ => Err(line.error(format!("expected `<destination> <source> <length>`, found `{}`", line.text))),
         }
     }
 }
@@ -117,16 +141,31 @@ impl FromStr for Mapping {
     type Err = AocError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let parts: Vec<&str> = s.split_ascii_whitespace().collect();
-        match parts.as_slice() {
-            [to, from, length] => {
-                Ok(Mapping {
-                    from: i64::from_str(from).map_err(|_| AocError::ParseError)?,
-                    to: i64::from_str(to).map_err(|_| AocError::ParseError)?,
-                    length: i64::from_str(length).map_err(|_| AocError::ParseError)?,
-                })
-            },
-            _ => Err(AocError::ParseError)
-        }
+        Mapping::parse(Span::new(s, 1))
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod test {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let s = std::fs::read_to_string("input/day_05/easy_test.txt").unwrap();
+        let almanac = Almanac::from_str(&s).unwrap();
+        assert_eq!(vec![79, 14, 55, 13], almanac.seeds);
+        assert_eq!(7, almanac.into_maps().len());
+    }
+
+    #[rstest]
+    #[case("seeds: 79 14", "seeds: 79 x", AocError::at(1, 11, "invalid seed `x`"))]
+    #[case("water-to-light", "water-to-lights", AocError::at(18, 1, "expected `water-to-light map:`, found `water-to-lights map:`"))]
+    #[case("88 18 7", "88 18", AocError::at(19, 1, "expected `<destination> <source> <length>`, found `88 18`"))]
+    #[case("\n\nlight-to-temperature", "\nlight-to-temperature", AocError::at(1, 1, "expected seeds and 7 maps, found 7 blocks"))]
+    fn test_invalid(#[case] from: &str, #[case] to: &str, #[case] expected: AocError) {
+        let s = std::fs::read_to_string("input/day_05/easy_test.txt").unwrap();
+        assert_eq!(Err(expected), Almanac::from_str(&s.replacen(from, to, 1)).map(|_| ()));
+    }
+}
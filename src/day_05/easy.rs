@@ -5,7 +5,7 @@ use crate::common::error::AocError;
 use super::common::Almanac;
 
 
-fn solve_file(filename: &str) -> Result<i64, AocError> {
+pub fn solve_file(filename: &str) -> Result<i64, AocError> {
     let s = std::fs::read_to_string(filename).map_err(|_| AocError::FileNotFound)?;
     let almanac = Almanac::from_str(s.as_str())?;
     let seeds = almanac.seeds.clone();
@@ -19,6 +19,7 @@ fn solve_file(filename: &str) -> Result<i64, AocError> {
 // Seed 55, soil 57, fertilizer 57, water 53, light 46, temperature 82, humidity 82, location 86.
 // Seed 13, soil 13, fertilizer 52, water 41, light 34, temperature 34, humidity 35, location 35.
 
+#[allow(unused)]
 pub fn solve() -> Result<i64, AocError> {
     solve_file("input/day_05/puzzle.txt")
 }
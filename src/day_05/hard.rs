@@ -130,7 +130,7 @@ fn map_seed_ranges(almanac: Almanac, ranges: Vec<Range>) -> Vec<Range> {
         .collect()
 }
 
-fn solve_almanac(almanac: Almanac) -> Result<i64, AocError> {
+pub fn solve_almanac(almanac: Almanac) -> Result<i64, AocError> {
     let ranges = to_ranges(&almanac.seeds)?;
     let locations = map_seed_ranges(almanac, ranges);
     locations.into_iter()
@@ -140,7 +140,7 @@ fn solve_almanac(almanac: Almanac) -> Result<i64, AocError> {
 
 }
 
-fn solve_file(filename: &str) -> Result<i64, AocError> {
+pub fn solve_file(filename: &str) -> Result<i64, AocError> {
     let s = std::fs::read_to_string(filename).map_err(|_| AocError::FileNotFound)?;
     let almanac = Almanac::from_str(s.as_str())?;
     solve_almanac(almanac)
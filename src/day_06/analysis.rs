@@ -0,0 +1,122 @@
+use crate::common::error::AocError;
+
+use super::common::{HoldInterval, Race, parse};
+
+#[derive(Debug, PartialEq)]
+pub struct RaceAnalysis {
+    pub race: Race,
+    pub winning_holds: Option<HoldInterval>,
+    pub optimal_hold: i64,
+    pub best_distance: i128,
+    pub margin: i128,
+    // the best hold only matches the record
+    pub ties_record: bool,
+}
+
+impl RaceAnalysis {
+    pub fn num_winning_holds(&self) -> i64 {
+        self.winning_holds.map_or(0, |interval| interval.len())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RaceSummary {
+    pub races: Vec<RaceAnalysis>,
+    // a single unwinnable race makes the product zero
+    pub product: i64,
+    pub sum: i64,
+}
+
+pub fn analyze_race(race: Race) -> Result<RaceAnalysis, AocError> {
+    Ok(RaceAnalysis {
+        race,
+        winning_holds: race.winning_holds()?,
+        optimal_hold: race.optimal_hold(),
+        best_distance: race.best_distance(),
+        margin: race.margin(),
+        ties_record: race.ties_record(),
+    })
+}
+
+pub fn analyze_races(races: &[Race]) -> Result<RaceSummary, AocError> {
+    let races: Vec<RaceAnalysis> = races.iter().copied().map(analyze_race).collect::<Result<_, _>>()?;
+    let counts: Vec<i64> = races.iter().map(RaceAnalysis::num_winning_holds).collect();
+    let product = counts.iter()
+        .try_fold(1i64, |product, count| product.checked_mul(*count))
+        .ok_or(AocError::Overflow)?;
+    let sum = counts.iter()
+        .try_fold(0i64, |sum, count| sum.checked_add(*count))
+        .ok_or(AocError::Overflow)?;
+    Ok(RaceSummary { races, product, sum })
+}
+
+#[allow(unused)]
+pub fn analyze_file(filename: &str) -> Result<RaceSummary, AocError> {
+    analyze_races(&parse(filename)?)
+}
+
+#[cfg(test)]
+mod test {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[test]
+    fn test_testcase() {
+        let summary = analyze_file("input/day_06/easy_test.txt").unwrap();
+        assert_eq!(288, summary.product);
+        assert_eq!(4 + 8 + 9, summary.sum);
+
+        let first = &summary.races[0];
+        assert_eq!(Some(HoldInterval { min: 2, max: 5 }), first.winning_holds);
+        assert_eq!((3, 12, 3), (first.optimal_hold, first.best_distance, first.margin));
+
+        // the third race ties the record at holds 10 and 20, so neither wins
+        assert_eq!(Some(HoldInterval { min: 11, max: 19 }), summary.races[2].winning_holds);
+    }
+
+    #[test]
+    fn test_solve() {
+        let summary = analyze_file("input/day_06/puzzle.txt").unwrap();
+        assert_eq!(345015, summary.product);
+    }
+
+    #[rstest]
+    #[case(Race { time: 4, distance: 4 }, None, 0)]
+    #[case(Race { time: 3, distance: 2 }, None, 0)]
+    #[case(Race { time: 3, distance: 5 }, None, -3)]
+    #[case(Race { time: 0, distance: 0 }, None, 0)]
+    #[case(Race { time: 4, distance: 3 }, Some(HoldInterval { min: 2, max: 2 }), 1)]
+    #[case(Race { time: 5, distance: -1 }, Some(HoldInterval { min: 0, max: 5 }), 7)]
+    fn test_degenerate(#[case] race: Race, #[case] expected: Option<HoldInterval>, #[case] margin: i128) {
+        let analysis = analyze_race(race).unwrap();
+        assert_eq!(expected, analysis.winning_holds);
+        assert_eq!(margin, analysis.margin);
+        assert_eq!(margin == 0, analysis.ties_record);
+    }
+
+    #[test]
+    fn test_exact_boundaries() {
+        // large enough that f64 cannot represent the discriminant exactly
+        let time = 3_000_000_001;
+        let best = 1_500_000_000 * 1_500_000_001;
+        for distance in [best - 1, best, best - 2_250_000_000_000, 2_249_999_999_999_999_999] {
+            let race = Race { time, distance };
+            let interval = race.winning_holds().unwrap();
+            if let Some(interval) = interval {
+                assert!(race.distance_for(interval.min) > distance as i128);
+                assert!(race.distance_for(interval.min - 1) <= distance as i128);
+                assert_eq!(time, interval.min + interval.max);
+            } else {
+                assert!(race.margin() <= 0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_unwinnable_product() {
+        let races = [Race { time: 7, distance: 9 }, Race { time: 4, distance: 4 }];
+        let summary = analyze_races(&races).unwrap();
+        assert_eq!((0, 4), (summary.product, summary.sum));
+    }
+}
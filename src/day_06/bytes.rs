@@ -0,0 +1,83 @@
+use crate::common::{bytes::{lines, numbers}, error::AocError};
+
+use super::common::{Race, num_possibilities};
+
+fn to_i64(number: Result<u64, AocError>) -> Result<i64, AocError> {
+    i64::try_from(number?).map_err(|_| AocError::Overflow)
+}
+
+fn labeled<'a>(line: Option<&'a [u8]>, label: &[u8], line_number: usize) -> Result<&'a [u8], AocError> {
+    line.and_then(|line| line.strip_prefix(label))
+        .ok_or_else(|| AocError::at(line_number, 1, format!("expected `{}`", String::from_utf8_lossy(label))))
+}
+
+fn sheet(input: &[u8]) -> Result<(&[u8], &[u8]), AocError> {
+    let mut lines = lines(input);
+    Ok((labeled(lines.next(), b"Time:", 1)?, labeled(lines.next(), b"Distance:", 2)?))
+}
+
+// all digits of a line as one number, ignoring the spaces between them
+fn kerned(line: &[u8]) -> Result<i64, AocError> {
+    line.iter()
+        .filter(|b| !b.is_ascii_whitespace())
+        .try_fold(0i64, |value, b| {
+            if !b.is_ascii_digit() {
+                return Err(AocError::ParseError);
+            }
+            value.checked_mul(10)
+                .and_then(|value| value.checked_add((b - b'0') as i64))
+                .ok_or(AocError::Overflow)
+        })
+}
+
+pub fn solve_easy(input: &[u8]) -> Result<i64, AocError> {
+    let (times, distances) = sheet(input)?;
+    let (mut times, mut distances) = (numbers(times).map(to_i64), numbers(distances).map(to_i64));
+    let mut product = 1;
+    loop {
+        match (times.next(), distances.next()) {
+            (Some(time), Some(distance)) => {
+                let count = num_possibilities(Race { time: time?, distance: distance? })?;
+                product = count.checked_mul(product).ok_or(AocError::Overflow)?;
+            }
+            (None, None) => return Ok(product),
+            _ => return Err(AocError::at(2, 1, "expected as many distances as times")),
+        }
+    }
+}
+
+pub fn solve_hard(input: &[u8]) -> Result<i64, AocError> {
+    let (time, distance) = sheet(input)?;
+    num_possibilities(Race { time: kerned(time)?, distance: kerned(distance)? })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_testcase() {
+        let input = std::fs::read("input/day_06/easy_test.txt").unwrap();
+        assert_eq!(Ok(288), solve_easy(&input));
+        assert_eq!(Ok(71503), solve_hard(&input));
+    }
+
+    #[test]
+    fn test_solve() {
+        let input = std::fs::read("input/day_06/puzzle.txt").unwrap();
+        assert_eq!(Ok(345015), solve_easy(&input));
+        assert_eq!(Ok(42588603), solve_hard(&input));
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert_eq!(Err(AocError::at(2, 1, "expected as many distances as times")), solve_easy(b"Time: 1 2\nDistance: 3"));
+        assert_eq!(Err(AocError::at(2, 1, "expected `Distance:`")), solve_hard(b"Time: 1 2\n"));
+    }
+
+    #[test]
+    fn test_product_overflow() {
+        let input = b"Time: 4000000 4000000 4000000\nDistance: 0 0 0";
+        assert_eq!(Err(AocError::Overflow), solve_easy(input));
+    }
+}
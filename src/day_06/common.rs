@@ -1,63 +1,255 @@
 use std::str::FromStr;
 
-use crate::common::{error::AocError, traits::ToAocError};
+use crate::common::{error::AocError, parsing::Span, traits::ToAocError};
 
 
 
-#[derive(Debug)]
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Race {
     pub time: i64,
     pub distance: i64,
 }
 
-pub fn num_possibilities(race: Race) -> i64 {
-    let p = -race.time as f64;
-    let q = race.distance as f64;
-    let x_0 = -p/2.0 + f64::sqrt(p*p/4.0 - q);
-    let x_1 = -p/2.0 - f64::sqrt(p*p/4.0 - q);
+// an inclusive range of button hold times
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HoldInterval {
+    pub min: i64,
+    pub max: i64,
+}
+
+impl HoldInterval {
+    pub fn len(&self) -> i64 {
+        self.max - self.min + 1
+    }
+}
+
+// all arithmetic is done on integers, distances are i128 since the best
+// distance grows with the square of the race time
+impl Race {
+    pub fn distance_for(&self, hold: i64) -> i128 {
+        if hold < 0 || hold > self.time {
+            return 0;
+        }
+        hold as i128 * (self.time - hold) as i128
+    }
+
+    // `time - optimal_hold()` is equally good when the time is odd
+    pub fn optimal_hold(&self) -> i64 {
+        self.time.max(0) / 2
+    }
+
+    pub fn best_distance(&self) -> i128 {
+        self.distance_for(self.optimal_hold())
+    }
+
+    // negative when the record cannot be beaten, zero when it can only be tied
+    pub fn margin(&self) -> i128 {
+        self.best_distance() - self.distance as i128
+    }
+
+    pub fn ties_record(&self) -> bool {
+        self.margin() == 0
+    }
+
+    // hold * (time - hold) = -hold^2 + time * hold
+    pub fn winning_holds(&self) -> Result<Option<HoldInterval>, AocError> {
+        exact_winning_holds(*self, (-1, self.time as i128, 0))
+    }
+
+    pub fn num_winning_holds(&self) -> Result<i64, AocError> {
+        Ok(self.winning_holds()?.map_or(0, |interval| interval.len()))
+    }
+}
+
+// the holds in `0..=time` for which a*hold^2 + b*hold + c beats the record,
+// where a < 0. they lie strictly between the roots of the quadratic, which
+// the integer square root gives up to rounding, corrected by stepping to the
+// exact boundaries
+pub fn exact_winning_holds(race: Race, (a, b, c): (i128, i128, i128)) -> Result<Option<HoldInterval>, AocError> {
+    if race.time < 0 || a >= 0 {
+        return Ok(None);
+    }
+    let value = |hold: i64| {
+        a.checked_mul(hold as i128)
+            .and_then(|value| value.checked_add(b))
+            .and_then(|value| value.checked_mul(hold as i128))
+            .and_then(|value| value.checked_add(c))
+            .ok_or(AocError::Overflow)
+    };
+    let wins = |hold: i64| Ok::<_, AocError>(value(hold)? > race.distance as i128);
+    let two_a = a.checked_mul(-2).ok_or(AocError::Overflow)?;
+    let mut peak = (b / two_a).clamp(0, race.time as i128) as i64;
+    if peak < race.time && value(peak + 1)? > value(peak)? {
+        peak += 1;
+    }
+    if !wins(peak)? {
+        return Ok(None);
+    }
+    let discriminant = (race.distance as i128).checked_sub(c)
+        .and_then(|d| two_a.checked_mul(2)?.checked_mul(d))
+        .and_then(|four_a_d| b.checked_mul(b)?.checked_sub(four_a_d))
+        .ok_or(AocError::Overflow)?;
+    let root = discriminant.max(0).isqrt();
+    let mut min = (b.checked_sub(root).ok_or(AocError::Overflow)? / two_a).clamp(0, peak as i128) as i64;
+    let mut max = (b.checked_add(root).ok_or(AocError::Overflow)? / two_a).clamp(peak as i128, race.time as i128) as i64;
+    while !wins(min)? {
+        min += 1;
+    }
+    while min > 0 && wins(min - 1)? {
+        min -= 1;
+    }
+    while !wins(max)? {
+        max -= 1;
+    }
+    while max < race.time && wins(max + 1)? {
+        max += 1;
+    }
+    Ok(Some(HoldInterval { min, max }))
+}
+
+pub fn num_possibilities(race: Race) -> Result<i64, AocError> {
+    race.num_winning_holds()
+}
+
 
-    (x_0.ceil() - x_1.floor() - 1.0) as i64
+#[allow(unused)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Kerning {
+    // every column is its own race
+    Separate,
+    // the digits of all columns form a single race
+    Kerned,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct Column {
+    pub value: i64,
+    pub digits: String,
+    pub column: usize,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RaceSheet {
+    pub times: Vec<Column>,
+    pub distances: Vec<Column>,
+}
 
-fn to_vec(line: &str) -> Result<Vec<i64>, AocError> {
-    line.split_ascii_whitespace()
-        .skip(1)
-        .map(|s| i64::from_str(s).to_aoc_error(AocError::ParseError))
+fn parse_columns(line: Span, label: &str) -> Result<Vec<Column>, AocError> {
+    line.labeled(label)?
+        .words()
+        .map(|digits| {
+            if !digits.text.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(digits.error(format!("invalid number `{}`", digits.text)));
+            }
+            let value = digits.parse("number")?;
+            Ok(Column { value, digits: digits.text.to_string(), column: digits.column })
+        })
         .collect()
 }
 
-pub fn parse(filename: &str) -> Result<Vec<Race>, AocError> {
+fn kern(columns: &[Column]) -> Result<i64, AocError> {
+    let digits: String = columns.iter().map(|column| column.digits.as_str()).collect();
+    i64::from_str(&digits).map_err(|_| AocError::Overflow)
+}
+
+impl RaceSheet {
+    // Time:      7  15   30
+    // Distance:  9  40  200
+    pub fn parse(s: &str) -> Result<Self, AocError> {
+        let document = Span::document(s);
+        let mut lines = document.lines();
+        let times = parse_columns(lines.next().unwrap_or(Span::new("", 1)), "Time:")?;
+        let distance_line = lines.next().unwrap_or(Span::new("", 2));
+        let distances = parse_columns(distance_line, "Distance:")?;
+        if times.is_empty() {
+            return Err(AocError::at(1, 1, "expected at least one race"));
+        }
+        if times.len() != distances.len() {
+            let reason = format!("expected {} distances, found {}", times.len(), distances.len());
+            // the first surplus distance, or the end of the line when distances are missing
+            let column = distances.get(times.len())
+                .map_or(distance_line.text.trim_end().len() + 1, |extra| extra.column);
+            return Err(AocError::at(2, column, reason));
+        }
+        if let Some(line) = lines.find(|line| !line.is_blank()) {
+            return Err(line.error("unexpected line after `Distance:`"));
+        }
+        Ok(RaceSheet { times, distances })
+    }
+
+    pub fn races(&self) -> Vec<Race> {
+        self.times.iter().zip(&self.distances)
+            .map(|(time, distance)| Race { time: time.value, distance: distance.value })
+            .collect()
+    }
+
+    pub fn kerned_race(&self) -> Result<Race, AocError> {
+        Ok(Race { time: kern(&self.times)?, distance: kern(&self.distances)? })
+    }
+
+    pub fn races_with(&self, kerning: Kerning) -> Result<Vec<Race>, AocError> {
+        match kerning {
+            Kerning::Separate => Ok(self.races()),
+            Kerning::Kerned => Ok(vec![self.kerned_race()?]),
+        }
+    }
+}
+
+pub fn get_race_sheet(filename: &str) -> Result<RaceSheet, AocError> {
     let s = std::fs::read_to_string(filename)
         .to_aoc_error(AocError::FileNotFound)?;
-    let mut lines = s.lines();
-    let time_str = lines.next().ok_or(AocError::ParseError)?;
-    let distance_str = lines.next().ok_or(AocError::ParseError)?;
-
-    let times = to_vec(time_str)?;
-    let distances = to_vec(distance_str)?;
+    RaceSheet::parse(&s)
+}
 
-    Ok(times.into_iter().zip(distances)
-        .map(|(time, distance)| Race { time, distance })
-        .collect())
+pub fn parse(filename: &str) -> Result<Vec<Race>, AocError> {
+    get_race_sheet(filename)?.races_with(Kerning::Separate)
 }
 
 pub fn parse_as_single(filename: &str) -> Result<Race, AocError> {
-    let s = std::fs::read_to_string(filename)
-    .to_aoc_error(AocError::FileNotFound)?;
-    let s = s.replace(' ', "");
-    let mut lines = s.lines();
-    let time_str = lines.next().ok_or(AocError::ParseError)?;
-    let distance_str = lines.next().ok_or(AocError::ParseError)?;
-
-    let time = time_str.split(':').last().ok_or(AocError::ParseError)
-        .map(|s| i64::from_str(s).to_aoc_error(AocError::ParseError))??;
-    let distance = distance_str.split(':').last().ok_or(AocError::ParseError)
-        .map(|s| i64::from_str(s).to_aoc_error(AocError::ParseError))??;
-
-    Ok(Race {
-        time,
-        distance,
-    })
-
-}
\ No newline at end of file
+    get_race_sheet(filename)?.kerned_race()
+}
+
+#[cfg(test)]
+mod test {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[test]
+    fn test_kerning() {
+        let sheet = get_race_sheet("input/day_06/easy_test.txt").unwrap();
+        assert_eq!(Ok(vec![
+            Race { time: 7, distance: 9 },
+            Race { time: 15, distance: 40 },
+            Race { time: 30, distance: 200 },
+        ]), sheet.races_with(Kerning::Separate));
+        assert_eq!(Ok(vec![Race { time: 71530, distance: 940200 }]), sheet.races_with(Kerning::Kerned));
+    }
+
+    #[test]
+    fn test_kerning_keeps_leading_zeros() {
+        let sheet = RaceSheet::parse("Time: 7 05\nDistance: 1 0").unwrap();
+        assert_eq!(Ok(Race { time: 705, distance: 10 }), sheet.kerned_race());
+    }
+
+    #[rstest]
+    #[case("Time: 7 15\nDistance: 9", AocError::at(2, 12, "expected 2 distances, found 1"))]
+    #[case("Time: 7\nDistance: 9 40", AocError::at(2, 13, "expected 1 distances, found 2"))]
+    #[case("Tim: 7\nDistance: 9", AocError::at(1, 1, "expected `Time:`, found `Tim:`"))]
+    #[case("Time: 7", AocError::at(2, 1, "expected `Distance:`, found ``"))]
+    #[case("Time: 7 x5\nDistance: 9 40", AocError::at(1, 9, "invalid number `x5`"))]
+    #[case("Time: 7\nDistance: -9", AocError::at(2, 11, "invalid number `-9`"))]
+    #[case("Time:\nDistance:", AocError::at(1, 1, "expected at least one race"))]
+    #[case("Time: 7\nDistance: 9\nTime: 8", AocError::at(3, 1, "unexpected line after `Distance:`"))]
+    fn test_invalid(#[case] input: &str, #[case] expected: AocError) {
+        assert_eq!(Err(expected), RaceSheet::parse(input));
+    }
+
+    #[test]
+    fn test_kerned_overflow() {
+        let sheet = RaceSheet::parse("Time: 9999999999 9999999999\nDistance: 1 2").unwrap();
+        assert_eq!(Err(AocError::Overflow), sheet.kerned_race());
+        assert_eq!(2, sheet.races().len());
+    }
+}
@@ -1,13 +1,12 @@
 use crate::{common::error::AocError, day_06::common::{parse, num_possibilities}};
 
-fn solve_file(filename: &str) -> Result<i64, AocError> {
+pub fn solve_file(filename: &str) -> Result<i64, AocError> {
     let races = parse(filename)?;
-    let possibilities: i64 = races.into_iter()
-        .map(num_possibilities)
-        .product();
-    Ok(possibilities)
+    races.into_iter()
+        .try_fold(1i64, |product, race| product.checked_mul(num_possibilities(race)?).ok_or(AocError::Overflow))
 }
 
+#[allow(unused)]
 pub fn solve() -> Result<i64, AocError> {
     solve_file("input/day_06/puzzle.txt")
 }
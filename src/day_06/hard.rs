@@ -3,11 +3,12 @@ use crate::{common::error::AocError, day_06::common::parse_as_single};
 use super::common::num_possibilities;
 
 
-fn solve_file(filename: &str) -> Result<i64, AocError> {
+pub fn solve_file(filename: &str) -> Result<i64, AocError> {
     let race = parse_as_single(filename)?;
-    Ok(num_possibilities(race))
+    num_possibilities(race)
 }
 
+#[allow(unused)]
 pub fn solve() -> Result<i64, AocError> {
     solve_file("input/day_06/puzzle.txt")
 }
@@ -0,0 +1,194 @@
+use crate::common::error::AocError;
+
+use super::common::{HoldInterval, Race, exact_winning_holds};
+
+// a model must have a single peak over the holds `0..=time`, distances may
+// stay flat only at the peak, which holds for any concave model
+pub trait BoatModel {
+    fn distance(&self, time: i64, hold: i64) -> i128;
+
+    // (a, b, c) when the distance is exactly a*hold^2 + b*hold + c for every
+    // hold of a race of the given time, enables the exact solver
+    fn quadratic(&self, _time: i64) -> Option<(i128, i128, i128)> {
+        None
+    }
+}
+
+// the boat gains `acceleration` mm/ms of speed per ms held, starting from
+// `start_speed` and never exceeding `max_speed`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Kinematics {
+    acceleration: i64,
+    start_speed: i64,
+    max_speed: Option<i64>,
+}
+
+impl Default for Kinematics {
+    // the puzzle rules
+    fn default() -> Self {
+        Kinematics { acceleration: 1, start_speed: 0, max_speed: None }
+    }
+}
+
+impl Kinematics {
+    // negative values would make the distance convex in the hold time, which
+    // can split the winning holds into two intervals
+    #[allow(unused)]
+    pub fn new(acceleration: i64, start_speed: i64, max_speed: Option<i64>) -> Result<Self, AocError> {
+        if acceleration < 0 || start_speed < 0 || max_speed.is_some_and(|max| max < 0) {
+            return Err(AocError::SolveError);
+        }
+        Ok(Kinematics { acceleration, start_speed, max_speed })
+    }
+
+    fn uncapped_speed(&self, hold: i64) -> i128 {
+        self.start_speed as i128 + self.acceleration as i128 * hold as i128
+    }
+
+    fn speed(&self, hold: i64) -> i128 {
+        let speed = self.uncapped_speed(hold);
+        self.max_speed.map_or(speed, |max| speed.min(max as i128))
+    }
+}
+
+impl BoatModel for Kinematics {
+    fn distance(&self, time: i64, hold: i64) -> i128 {
+        self.speed(hold) * (time - hold) as i128
+    }
+
+    // (start + acceleration * hold) * (time - hold), unless the cap is reached
+    fn quadratic(&self, time: i64) -> Option<(i128, i128, i128)> {
+        if self.max_speed.is_some_and(|max| (max as i128) < self.uncapped_speed(time.max(0))) {
+            return None;
+        }
+        let (acceleration, start, time) = (self.acceleration as i128, self.start_speed as i128, time as i128);
+        Some((-acceleration, acceleration * time - start, start * time))
+    }
+}
+
+fn wins(model: &impl BoatModel, race: Race, hold: i64) -> bool {
+    model.distance(race.time, hold) > race.distance as i128
+}
+
+// first hold in `lo..=hi` for which `pred` holds, where `pred` is monotone
+fn partition_point(mut lo: i64, mut hi: i64, pred: impl Fn(i64) -> bool) -> i64 {
+    hi += 1;
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if pred(mid) {
+            hi = mid;
+        } else {
+            lo = mid + 1;
+        }
+    }
+    lo
+}
+
+// the peak of a single-peaked model, by binary search on the slope
+fn peak(model: &impl BoatModel, time: i64) -> i64 {
+    partition_point(0, time - 1, |hold| model.distance(time, hold) >= model.distance(time, hold + 1))
+}
+
+fn interval_around(model: &impl BoatModel, race: Race, peak: i64) -> Option<HoldInterval> {
+    if !wins(model, race, peak) {
+        return None;
+    }
+    let min = partition_point(0, peak, |hold| wins(model, race, hold));
+    let max = partition_point(peak, race.time, |hold| !wins(model, race, hold)) - 1;
+    Some(HoldInterval { min, max })
+}
+
+fn search(model: &impl BoatModel, race: Race) -> Option<HoldInterval> {
+    interval_around(model, race, peak(model, race.time))
+}
+
+#[allow(unused)]
+pub fn winning_holds_with(race: Race, model: &impl BoatModel) -> Result<Option<HoldInterval>, AocError> {
+    if race.time < 0 {
+        return Ok(None);
+    }
+    match model.quadratic(race.time) {
+        Some((a, b, c)) if a < 0 => exact_winning_holds(race, (a, b, c)),
+        _ => Ok(search(model, race)),
+    }
+}
+
+#[allow(unused)]
+pub fn num_winning_holds_with(race: Race, model: &impl BoatModel) -> Result<i64, AocError> {
+    Ok(winning_holds_with(race, model)?.map_or(0, |interval| interval.len()))
+}
+
+#[cfg(test)]
+mod test {
+    use rstest::rstest;
+
+    use crate::day_06::common::parse;
+
+    use super::*;
+
+    #[test]
+    fn test_testcase() {
+        let races = parse("input/day_06/easy_test.txt").unwrap();
+        let product: i64 = races.into_iter()
+            .map(|race| num_winning_holds_with(race, &Kinematics::default()).unwrap())
+            .product();
+        assert_eq!(288, product);
+    }
+
+    #[test]
+    fn test_default_matches_race() {
+        for time in 0..60 {
+            for distance in -1..time * time / 4 + 2 {
+                let race = Race { time, distance };
+                let model = Kinematics::default();
+                assert_eq!(race.winning_holds(), winning_holds_with(race, &model));
+                assert_eq!(race.winning_holds(), Ok(search(&model, race)));
+            }
+        }
+    }
+
+    #[rstest]
+    #[case(Kinematics::new(2, 0, None), 10, 40, Some(HoldInterval { min: 3, max: 7 }))]
+    #[case(Kinematics::new(1, 3, None), 10, 40, Some(HoldInterval { min: 3, max: 4 }))]
+    #[case(Kinematics::new(1, 0, Some(3)), 10, 20, Some(HoldInterval { min: 3, max: 3 }))]
+    #[case(Kinematics::new(1, 0, Some(3)), 10, 21, None)]
+    #[case(Kinematics::new(0, 5, None), 10, 20, Some(HoldInterval { min: 0, max: 5 }))]
+    fn test_variants(
+        #[case] model: Result<Kinematics, AocError>,
+        #[case] time: i64,
+        #[case] distance: i64,
+        #[case] expected: Option<HoldInterval>,
+    ) {
+        let model = model.unwrap();
+        let race = Race { time, distance };
+        assert_eq!(Ok(expected), winning_holds_with(race, &model));
+        assert_eq!(expected, search(&model, race));
+    }
+
+    #[test]
+    fn test_exhaustive_variants() {
+        for (acceleration, start_speed, max_speed) in [(1, 2, None), (3, 0, Some(10)), (2, 5, Some(4)), (0, 0, None)] {
+            let model = Kinematics::new(acceleration, start_speed, max_speed).unwrap();
+            for time in 0..30 {
+                for distance in -1..200 {
+                    let race = Race { time, distance };
+                    let winning: Vec<i64> = (0..=time).filter(|hold| wins(&model, race, *hold)).collect();
+                    let expected = winning.first().map(|min| HoldInterval { min: *min, max: *winning.last().unwrap() });
+                    assert_eq!(Ok(expected), winning_holds_with(race, &model), "{model:?} {race:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_exact_overflow() {
+        let model = Kinematics::new(i64::MAX, 0, None).unwrap();
+        let race = Race { time: 1 << 62, distance: 0 };
+        assert_eq!(Err(AocError::Overflow), winning_holds_with(race, &model));
+    }
+
+    #[test]
+    fn test_invalid_model() {
+        assert_eq!(Err(AocError::SolveError), Kinematics::new(-1, 0, None));
+    }
+}
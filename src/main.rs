@@ -1,33 +1,55 @@
 mod common {
+    #[cfg(test)]
+    pub mod bench;
+    pub mod bytes;
     pub mod functions;
     pub mod error;
+    pub mod input;
+    pub mod matcher;
+    pub mod parsing;
+    pub mod table;
     pub mod traits;
 }
 
 mod day_01 {
+    pub mod bytes;
     pub mod easy;
     pub mod hard;
+    pub mod report;
+    pub mod vocabulary;
 }
 
 mod day_02 {
+    pub mod analysis;
+    pub mod bytes;
     pub mod common;
+    pub mod constraint;
     pub mod easy;
     pub mod hard;
 }
 
 mod day_03 {
+    pub mod analysis;
+    pub mod bytes;
     pub mod common;
     pub mod easy;
     pub mod hard;
+    pub mod parallel;
+    pub mod streaming;
 }
 
 mod day_04 {
+    pub mod bitset;
+    pub mod bytes;
+    pub mod cascade;
     pub mod common;
     pub mod easy;
     pub mod hard;
+    pub mod scoring;
 }
 
 mod day_05 {
+    pub mod bytes;
     pub mod common;
     pub mod easy;
     pub mod hard;
@@ -35,35 +57,216 @@ mod day_05 {
 }
 
 mod day_06 {
+    pub mod analysis;
+    pub mod bytes;
     pub mod common;
     pub mod easy;
     pub mod hard;
+    pub mod model;
 }
 
 
+mod batch;
+mod cli;
+mod registry;
+mod remote {
+    pub mod http;
+    pub mod inputs;
+    #[cfg(test)]
+    pub mod mock;
+    pub mod submit;
+}
+mod runner;
+mod scaffold;
+mod watch;
+
+use clap::Parser;
+
+use cli::{Cli, Command};
+use common::error::AocError;
+
+fn fail(message: String) -> ! {
+    eprintln!("{message}");
+    std::process::exit(1);
+}
+
+fn check_variant(variant: &str) {
+    if !registry::variants().contains(&variant) {
+        fail(format!("unknown variant `{variant}`, expected one of: {}", registry::variants().join(", ")));
+    }
+}
+
+fn run(variant: &str) {
+    check_variant(variant);
+    let solutions = registry::select(variant);
+    for solution in solutions {
+        if !solution.has_puzzle_input() {
+            println!("day {} ({}): no puzzle input, run `fetch --day {}`", solution.day, solution.part, solution.day);
+            continue;
+        }
+        match (solution.solve)(&solution.puzzle_input()) {
+            Ok(answer) => println!("day {} ({}): {}", solution.day, solution.part, answer),
+            Err(error) => {
+                eprintln!("day {} ({}): {:?}", solution.day, solution.part, error);
+                std::process::exit(1);
+            }
+        }
+        if solution.part == registry::Part::Hard {
+            println!();
+        }
+    }
+}
+
+fn run_all(variant: Option<String>, threads: Option<usize>) {
+    if let Some(variant) = &variant {
+        check_variant(variant);
+    }
+    let jobs = registry::solutions().into_iter()
+        .filter(|solution| variant.as_ref().is_none_or(|variant| solution.variant == variant))
+        .map(|solution| (solution, solution.puzzle_input()))
+        .collect();
+    let results = runner::run_parallel(jobs, threads)
+        .unwrap_or_else(|error| fail(format!("could not start the worker threads: {error:?}")));
+    print!("{}", runner::format_table(&results));
+    if !results.iter().all(runner::RunResult::succeeded) {
+        std::process::exit(1);
+    }
+}
+
+fn run_batch(dir: &str, answers: &str, variant: Option<String>, threads: Option<usize>) {
+    if let Some(variant) = &variant {
+        check_variant(variant);
+    }
+    let inputs = batch::discover(dir)
+        .unwrap_or_else(|error| fail(format!("could not list the inputs under {dir}: {error:?}")));
+    let answers = batch::load_answers(answers)
+        .unwrap_or_else(|error| fail(format!("could not read the answers in {answers}: {error:?}")));
+    let solutions: Vec<registry::Solution> = registry::solutions().into_iter()
+        .filter(|solution| variant.as_ref().is_none_or(|variant| solution.variant == variant))
+        .collect();
+    let results = batch::run_batch(&inputs, &solutions, &answers, threads)
+        .unwrap_or_else(|error| fail(format!("could not start the worker threads: {error:?}")));
+    print!("{}", batch::format_matrix(&results));
+    if results.iter().any(|result| result.verdict() == batch::Verdict::Wrong) {
+        std::process::exit(1);
+    }
+}
+
+fn fetch(day: Option<u32>, dir: &str, base_url: &str, year: u32, session_file: &str) {
+    let session = remote::http::session_token(session_file)
+        .unwrap_or_else(|error| fail(format!("could not read the session token: {error:?}")));
+    let client = remote::http::Client::new(base_url, year, &session, remote::http::MIN_INTERVAL);
+    let cache = remote::inputs::InputCache::new(dir, client);
+    let mut days: Vec<u32> = registry::solutions().iter().map(|solution| solution.day).collect();
+    days.dedup();
+    for day in day.map_or(days, |day| vec![day]) {
+        let cached = cache.ensure(day)
+            .unwrap_or_else(|error| fail(format!("day {day}: could not download the puzzle input: {error:?}")));
+        match cached {
+            remote::inputs::Cached::Hit => println!("day {day}: already cached at {}", cache.path(day).display()),
+            remote::inputs::Cached::Fetched => println!("day {day}: downloaded to {}", cache.path(day).display()),
+        }
+    }
+}
+
+struct Submit {
+    day: u32,
+    part: registry::Part,
+    answer: Option<String>,
+    log: String,
+    base_url: String,
+    year: u32,
+    session_file: String,
+}
+
+fn submit(args: Submit) {
+    let answer = args.answer.unwrap_or_else(|| {
+        let Some(solution) = registry::select(registry::IDIOMATIC).into_iter()
+            .find(|solution| solution.day == args.day && solution.part == args.part) else {
+            fail(format!("day {} ({}) has no solution", args.day, args.part));
+        };
+        if !solution.has_puzzle_input() {
+            fail(format!("day {} has no puzzle input, run `fetch --day {}`", args.day, args.day));
+        }
+        (solution.solve)(&solution.puzzle_input())
+            .unwrap_or_else(|error| fail(format!("day {} ({}): {:?}", args.day, args.part, error)))
+    });
+    let session = remote::http::session_token(&args.session_file)
+        .unwrap_or_else(|error| fail(format!("could not read the session token: {error:?}")));
+    let client = remote::http::Client::new(&args.base_url, args.year, &session, remote::http::MIN_INTERVAL);
+    let mut log = remote::submit::AttemptLog::load(&args.log)
+        .unwrap_or_else(|error| fail(format!("could not read the attempt log {}: {error:?}", args.log)));
+    let submission = remote::submit::submit(&client, &mut log, args.day, args.part, &answer)
+        .unwrap_or_else(|error| fail(format!("day {} ({}) {answer}: {error:?}", args.day, args.part)));
+    match submission {
+        remote::submit::Submission::Sent(verdict) => println!("day {} ({}) {answer}: {verdict}", args.day, args.part),
+        remote::submit::Submission::Refused(reason) => {
+            eprintln!("not submitted: {reason}");
+            std::process::exit(1);
+        }
+    }
+}
+
+fn new_day(day: u32) {
+    let created = scaffold::new_day(std::path::Path::new("."), day).unwrap_or_else(|error| match error {
+        AocError::Scaffold { reason } => fail(reason),
+        error => fail(format!("could not scaffold day {day}: {error:?}")),
+    });
+    for path in created {
+        println!("created {}", path.display());
+    }
+    println!("registered day_{day:02} in src/main.rs and src/registry.rs");
+    println!("run `fetch --day {day}` to replace the empty puzzle input");
+}
+
+fn watch(day: u32, variant: &str, dir: &str, answers: &str, puzzle: bool, interval: u64) {
+    check_variant(variant);
+    let solutions = registry::select(variant);
+    if !solutions.iter().any(|solution| solution.day == day) {
+        fail(format!("day {day} has no {variant} solutions"));
+    }
+    let mut watcher = watch::Watcher::new(day, dir, answers, solutions, puzzle);
+    println!("watching {dir}/day_{day:02} with the {variant} solutions, ctrl-c to stop");
+    let mut last_error = None;
+    loop {
+        // failed inputs are retried on the next poll, the same error is only reported once
+        let changes = match watcher.poll() {
+            Ok(changes) => {
+                last_error = None;
+                changes
+            }
+            Err(error) => {
+                let error = format!("{error:?}");
+                if last_error.as_ref() != Some(&error) {
+                    eprintln!("could not check {dir}/day_{day:02}: {error}");
+                }
+                last_error = Some(error);
+                vec![]
+            }
+        };
+        for change in &changes {
+            println!("{}", watch::format_change(change));
+        }
+        if !changes.is_empty() {
+            println!();
+        }
+        std::thread::sleep(std::time::Duration::from_millis(interval));
+    }
+}
+
 fn main() {
-    println!("day 1 (easy): {}", day_01::easy::solve().unwrap());
-    println!("day 1 (hard): {}", day_01::hard::solve().unwrap());
-    println!();
-
-    println!("day 2 (easy): {}", day_02::easy::solve().unwrap());
-    println!("day 2 (hard): {}", day_02::hard::solve().unwrap());
-    println!();
-
-    println!("day 3 (easy): {}", day_03::easy::solve().unwrap());
-    println!("day 3 (hard): {}", day_03::hard::solve().unwrap());
-    println!();
-
-    println!("day 4 (easy): {}", day_04::easy::solve().unwrap());
-    println!("day 4 (hard): {}", day_04::hard::solve().unwrap());
-    println!();
-
-    println!("day 5 (easy): {}", day_05::easy::solve().unwrap());
-    println!("day 5 (hard): {}", day_05::hard::solve().unwrap());
-    // println!("day 5 (hard bruteforce): {}", day_05::hard_bruteforce::solve().unwrap());
-    println!();
-
-    println!("day 6 (easy): {}", day_06::easy::solve().unwrap());
-    println!("day 6 (hard): {}", day_06::hard::solve().unwrap());
-    println!();
+    match Cli::parse().command {
+        None => run(registry::IDIOMATIC),
+        Some(Command::Run { variant }) => run(&variant),
+        Some(Command::All { variant, threads }) => run_all(variant, threads),
+        Some(Command::Batch { dir, answers, variant, threads }) => run_batch(&dir, &answers, variant, threads),
+        Some(Command::Fetch { day, dir, base_url, year, session_file }) => fetch(day, &dir, &base_url, year, &session_file),
+        Some(Command::Submit { day, part, answer, log, base_url, year, session_file }) => {
+            submit(Submit { day, part, answer, log, base_url, year, session_file })
+        }
+        Some(Command::NewDay { day }) => new_day(day),
+        Some(Command::Watch { day, variant, dir, answers, puzzle, interval }) => {
+            watch(day, &variant, &dir, &answers, puzzle, interval)
+        }
+    }
 }
@@ -0,0 +1,175 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::common::{error::AocError, input::{Loader, load}};
+use crate::{day_01, day_02, day_03, day_04, day_05, day_06};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Part {
+    Easy,
+    Hard,
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::Easy => write!(f, "easy"),
+            Part::Hard => write!(f, "hard"),
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "easy" => Ok(Part::Easy),
+            "hard" => Ok(Part::Hard),
+            _ => Err(format!("expected `easy` or `hard`, found `{s}`")),
+        }
+    }
+}
+
+// one way of solving one part of a day, answers are rendered to strings so
+// solutions with different answer types can be run side by side
+#[derive(Debug, Clone, Copy)]
+pub struct Solution {
+    pub day: u32,
+    pub part: Part,
+    pub variant: &'static str,
+    pub solve: fn(&str) -> Result<String, AocError>,
+}
+
+impl Solution {
+    pub fn puzzle_input(&self) -> String {
+        format!("input/day_{:02}/puzzle.txt", self.day)
+    }
+
+    // a freshly scaffolded day only has an empty placeholder until it is fetched
+    pub fn has_puzzle_input(&self) -> bool {
+        std::fs::metadata(self.puzzle_input()).is_ok_and(|metadata| metadata.len() > 0)
+    }
+}
+
+pub const IDIOMATIC: &str = "idiomatic";
+
+fn display<T: Display>(answer: Result<T, AocError>) -> Result<String, AocError> {
+    answer.map(|answer| answer.to_string())
+}
+
+fn bytes<T: Display>(filename: &str, loader: Loader, solve: fn(&[u8]) -> Result<T, AocError>) -> Result<String, AocError> {
+    display(solve(&load(filename, loader)?))
+}
+
+fn solution(day: u32, part: Part, variant: &'static str, solve: fn(&str) -> Result<String, AocError>) -> Solution {
+    Solution { day, part, variant, solve }
+}
+
+// every day has an idiomatic solution plus the zero-copy byte solvers, fed
+// either from a plain read or a memory map
+pub fn solutions() -> Vec<Solution> {
+    use Part::{Easy, Hard};
+    vec![
+        solution(1, Easy, IDIOMATIC, |f| display(day_01::easy::sum_file(f))),
+        solution(1, Hard, IDIOMATIC, |f| display(day_01::hard::sum_file(f))),
+        solution(1, Easy, "bytes", |f| bytes(f, Loader::Read, day_01::bytes::solve_easy)),
+        solution(1, Hard, "bytes", |f| bytes(f, Loader::Read, day_01::bytes::solve_hard)),
+        solution(1, Easy, "mmap", |f| bytes(f, Loader::Mmap, day_01::bytes::solve_easy)),
+        solution(1, Hard, "mmap", |f| bytes(f, Loader::Mmap, day_01::bytes::solve_hard)),
+
+        solution(2, Easy, IDIOMATIC, |f| display(day_02::easy::sum_possible_game_ids(f))),
+        solution(2, Hard, IDIOMATIC, |f| display(day_02::hard::sum_game_powers(f))),
+        solution(2, Easy, "bytes", |f| bytes(f, Loader::Read, day_02::bytes::solve_easy)),
+        solution(2, Hard, "bytes", |f| bytes(f, Loader::Read, day_02::bytes::solve_hard)),
+        solution(2, Easy, "mmap", |f| bytes(f, Loader::Mmap, day_02::bytes::solve_easy)),
+        solution(2, Hard, "mmap", |f| bytes(f, Loader::Mmap, day_02::bytes::solve_hard)),
+
+        solution(3, Easy, IDIOMATIC, |f| display(day_03::easy::solve_file(f))),
+        solution(3, Hard, IDIOMATIC, |f| display(day_03::hard::solve_file(f))),
+        solution(3, Easy, "bytes", |f| bytes(f, Loader::Read, day_03::bytes::solve_easy)),
+        solution(3, Hard, "bytes", |f| bytes(f, Loader::Read, day_03::bytes::solve_hard)),
+        solution(3, Easy, "mmap", |f| bytes(f, Loader::Mmap, day_03::bytes::solve_easy)),
+        solution(3, Hard, "mmap", |f| bytes(f, Loader::Mmap, day_03::bytes::solve_hard)),
+        solution(3, Easy, "streaming", |f| display(day_03::streaming::solve_file(f).map(|sums| sums.part_number_sum))),
+        solution(3, Hard, "streaming", |f| display(day_03::streaming::solve_file(f).map(|sums| sums.gear_ratio_sum))),
+        solution(3, Easy, "parallel", |f| display(day_03::parallel::solve_file(f).map(|sums| sums.part_number_sum))),
+        solution(3, Hard, "parallel", |f| display(day_03::parallel::solve_file(f).map(|sums| sums.gear_ratio_sum))),
+
+        solution(4, Easy, IDIOMATIC, |f| display(day_04::easy::solve_file(f))),
+        solution(4, Hard, IDIOMATIC, |f| display(day_04::hard::solve_file(f))),
+        solution(4, Easy, "bytes", |f| bytes(f, Loader::Read, day_04::bytes::solve_easy)),
+        solution(4, Hard, "bytes", |f| bytes(f, Loader::Read, day_04::bytes::solve_hard)),
+        solution(4, Easy, "mmap", |f| bytes(f, Loader::Mmap, day_04::bytes::solve_easy)),
+        solution(4, Hard, "mmap", |f| bytes(f, Loader::Mmap, day_04::bytes::solve_hard)),
+
+        solution(5, Easy, IDIOMATIC, |f| display(day_05::easy::solve_file(f))),
+        solution(5, Hard, IDIOMATIC, |f| display(day_05::hard::solve_file(f))),
+        solution(5, Easy, "bytes", |f| bytes(f, Loader::Read, day_05::bytes::solve_easy)),
+        solution(5, Hard, "bytes", |f| bytes(f, Loader::Read, day_05::bytes::solve_hard)),
+        solution(5, Easy, "mmap", |f| bytes(f, Loader::Mmap, day_05::bytes::solve_easy)),
+        solution(5, Hard, "mmap", |f| bytes(f, Loader::Mmap, day_05::bytes::solve_hard)),
+
+        solution(6, Easy, IDIOMATIC, |f| display(day_06::easy::solve_file(f))),
+        solution(6, Hard, IDIOMATIC, |f| display(day_06::hard::solve_file(f))),
+        solution(6, Easy, "bytes", |f| bytes(f, Loader::Read, day_06::bytes::solve_easy)),
+        solution(6, Hard, "bytes", |f| bytes(f, Loader::Read, day_06::bytes::solve_hard)),
+        solution(6, Easy, "mmap", |f| bytes(f, Loader::Mmap, day_06::bytes::solve_easy)),
+        solution(6, Hard, "mmap", |f| bytes(f, Loader::Mmap, day_06::bytes::solve_hard)),
+    ]
+}
+
+// in registration order
+pub fn variants() -> Vec<&'static str> {
+    let mut variants: Vec<&'static str> = vec![];
+    for solution in solutions() {
+        if !variants.contains(&solution.variant) {
+            variants.push(solution.variant);
+        }
+    }
+    variants
+}
+
+pub fn select(variant: &str) -> Vec<Solution> {
+    solutions().into_iter()
+        .filter(|solution| solution.variant == variant)
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::{BTreeMap, BTreeSet};
+
+    use crate::common::bench::measure;
+
+    use super::*;
+
+    #[test]
+    fn test_variants_agree() {
+        let mut answers: BTreeMap<(u32, Part), String> = BTreeMap::new();
+        for solution in solutions().into_iter().filter(Solution::has_puzzle_input) {
+            let answer = (solution.solve)(&solution.puzzle_input()).unwrap();
+            let expected = answers.entry((solution.day, solution.part)).or_insert(answer.clone());
+            assert_eq!(*expected, answer, "day {} ({}) {}", solution.day, solution.part, solution.variant);
+        }
+        assert!(answers.len() >= 12);
+    }
+
+    #[test]
+    fn test_select() {
+        assert_eq!(vec![IDIOMATIC, "bytes", "mmap", "streaming", "parallel"], variants());
+        let days: BTreeSet<u32> = solutions().iter().map(|solution| solution.day).collect();
+        assert_eq!(2 * days.len(), select(IDIOMATIC).len());
+        assert_eq!(2, select("streaming").len());
+        assert!(select("missing").is_empty());
+    }
+
+    #[test]
+    #[ignore = "benchmark, run with --release"]
+    fn bench_variants() {
+        for solution in solutions() {
+            let input = solution.puzzle_input();
+            let label = format!("day {} ({}) {}, 20 runs", solution.day, solution.part, solution.variant);
+            measure(&label, || (0..20).map(|_| (solution.solve)(&input)).collect::<Vec<_>>());
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use std::{
+    path::Path,
+    sync::Mutex,
+    thread::sleep,
+    time::{Duration, Instant},
+};
+
+use crate::common::error::AocError;
+
+pub const BASE_URL: &str = "https://adventofcode.com";
+pub const YEAR: u32 = 2023;
+pub const SESSION_VAR: &str = "AOC_SESSION";
+// the site asks automated tools to keep well below one request per few seconds
+pub const MIN_INTERVAL: Duration = Duration::from_secs(5);
+const USER_AGENT: &str = "aoc_2023 solutions runner";
+
+// the `session` cookie of a logged in browser, taken from the environment
+// first and then from a file that is kept out of git
+pub fn session_token(session_file: &str) -> Result<String, AocError> {
+    let token = match std::env::var(SESSION_VAR) {
+        Ok(token) => token,
+        Err(_) if Path::new(session_file).exists() => {
+            std::fs::read_to_string(session_file).map_err(|_| AocError::FileNotFound)?
+        }
+        Err(_) => return Err(AocError::Remote {
+            status: None,
+            reason: format!("no session token, set {SESSION_VAR} or write it to {session_file}"),
+        }),
+    };
+    Ok(token.trim().to_string())
+}
+
+// spaces requests out by at least `interval`, shared by every request of a client
+struct RateLimiter {
+    interval: Duration,
+    last: Mutex<Option<Instant>>,
+}
+
+impl RateLimiter {
+    fn wait(&self) {
+        let mut last = self.last.lock().unwrap();
+        if let Some(wait) = last.and_then(|last| self.interval.checked_sub(last.elapsed())) {
+            sleep(wait);
+        }
+        *last = Some(Instant::now());
+    }
+}
+
+pub struct Client {
+    base_url: String,
+    pub year: u32,
+    session: String,
+    agent: ureq::Agent,
+    limiter: RateLimiter,
+}
+
+impl Client {
+    pub fn new(base_url: &str, year: u32, session: &str, min_interval: Duration) -> Self {
+        Client {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            year,
+            session: session.to_string(),
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).timeout(Duration::from_secs(30)).build(),
+            limiter: RateLimiter { interval: min_interval, last: Mutex::new(None) },
+        }
+    }
+
+    pub fn day_path(&self, day: u32) -> String {
+        format!("/{}/day/{}", self.year, day)
+    }
+
+    pub fn get(&self, path: &str) -> Result<String, AocError> {
+        self.limiter.wait();
+        let request = self.agent.get(&format!("{}{}", self.base_url, path))
+            .set("Cookie", &format!("session={}", self.session));
+        read_response(request.call())
+    }
+
+    pub fn post_form(&self, path: &str, fields: &[(&str, &str)]) -> Result<String, AocError> {
+        self.limiter.wait();
+        let request = self.agent.post(&format!("{}{}", self.base_url, path))
+            .set("Cookie", &format!("session={}", self.session));
+        read_response(request.send_form(fields))
+    }
+}
+
+fn read_response(response: Result<ureq::Response, ureq::Error>) -> Result<String, AocError> {
+    match response {
+        Ok(response) => response.into_string()
+            .map_err(|error| AocError::Remote { status: None, reason: error.to_string() }),
+        Err(ureq::Error::Status(status, response)) => {
+            let body = response.into_string().unwrap_or_default();
+            Err(AocError::Remote { status: Some(status), reason: body.trim().to_string() })
+        }
+        Err(ureq::Error::Transport(transport)) => Err(AocError::Remote { status: None, reason: transport.to_string() }),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::remote::mock::MockServer;
+
+    use super::*;
+
+    #[test]
+    fn test_get() {
+        let server = MockServer::start(|request| match request.path.as_str() {
+            "/2023/day/1" => (200, "hello".to_string()),
+            _ => (404, "Not Found\n".to_string()),
+        });
+        let client = Client::new(&server.base_url, YEAR, "abc", Duration::ZERO);
+        assert_eq!(Ok("hello".to_string()), client.get(&client.day_path(1)));
+        assert_eq!(
+            Err(AocError::Remote { status: Some(404), reason: "Not Found".to_string() }),
+            client.get(&client.day_path(2)),
+        );
+        let requests = server.requests();
+        assert_eq!(Some("session=abc"), requests[0].header("cookie"));
+        assert_eq!(Some(USER_AGENT), requests[0].header("user-agent"));
+    }
+
+    #[test]
+    fn test_rate_limit() {
+        let server = MockServer::start(|_| (200, String::new()));
+        let client = Client::new(&server.base_url, YEAR, "abc", Duration::from_millis(100));
+        let start = Instant::now();
+        for _ in 0..3 {
+            client.get("/").unwrap();
+        }
+        assert!(start.elapsed() >= Duration::from_millis(200));
+    }
+}
@@ -0,0 +1,92 @@
+use std::path::{Path, PathBuf};
+
+use crate::common::error::AocError;
+
+use super::http::Client;
+
+pub trait InputSource {
+    fn fetch(&self, day: u32) -> Result<String, AocError>;
+}
+
+impl InputSource for Client {
+    fn fetch(&self, day: u32) -> Result<String, AocError> {
+        self.get(&format!("{}/input", self.day_path(day)))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Cached {
+    Hit,
+    Fetched,
+}
+
+// puzzle inputs live at `<root>/day_XX/puzzle.txt` like the hand copied ones,
+// a day is only ever fetched while that file is missing or is still the
+// empty placeholder `new-day` creates
+pub struct InputCache<S> {
+    root: PathBuf,
+    source: S,
+}
+
+impl<S: InputSource> InputCache<S> {
+    pub fn new(root: impl AsRef<Path>, source: S) -> Self {
+        InputCache { root: root.as_ref().to_path_buf(), source }
+    }
+
+    pub fn path(&self, day: u32) -> PathBuf {
+        self.root.join(format!("day_{day:02}")).join("puzzle.txt")
+    }
+
+    pub fn ensure(&self, day: u32) -> Result<Cached, AocError> {
+        let path = self.path(day);
+        if std::fs::metadata(&path).is_ok_and(|metadata| metadata.len() > 0) {
+            return Ok(Cached::Hit);
+        }
+        let input = self.source.fetch(day)?;
+        let dir = path.parent().ok_or(AocError::FileNotFound)?;
+        std::fs::create_dir_all(dir).map_err(|_| AocError::FileNotFound)?;
+        // written under a temporary name first so an interrupted run never
+        // leaves a truncated input that would count as cached
+        let partial = path.with_extension("txt.partial");
+        std::fs::write(&partial, input).map_err(|_| AocError::FileNotFound)?;
+        std::fs::rename(&partial, &path).map_err(|_| AocError::FileNotFound)?;
+        Ok(Cached::Fetched)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use crate::remote::{http::YEAR, mock::MockServer};
+
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc_2023_{name}_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_cache() {
+        let server = MockServer::start(|request| match request.path.as_str() {
+            "/2023/day/1/input" => (200, "1abc2\n".to_string()),
+            _ => (404, "Not Found".to_string()),
+        });
+        let dir = temp_dir("input_cache");
+        let cache = InputCache::new(&dir, Client::new(&server.base_url, YEAR, "abc", Duration::ZERO));
+
+        assert_eq!(Ok(Cached::Fetched), cache.ensure(1));
+        assert_eq!(Ok(Cached::Hit), cache.ensure(1));
+        assert_eq!("1abc2\n", std::fs::read_to_string(dir.join("day_01/puzzle.txt")).unwrap());
+        assert_eq!(1, server.requests().len());
+
+        std::fs::create_dir_all(dir.join("day_02")).unwrap();
+        std::fs::write(cache.path(2), "").unwrap();
+        assert_eq!(Err(AocError::Remote { status: Some(404), reason: "Not Found".to_string() }), cache.ensure(2));
+        assert_eq!(2, server.requests().len());
+        assert_eq!("", std::fs::read_to_string(cache.path(2)).unwrap());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,154 @@
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    sync::{Arc, Mutex, atomic::{AtomicBool, Ordering}},
+    thread::JoinHandle,
+};
+
+use crate::registry::Part;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MockRequest {
+    pub method: String,
+    pub path: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl MockRequest {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+type Handler = Box<dyn Fn(&MockRequest) -> (u16, String) + Send + Sync>;
+
+// a plain http server on a free local port that answers every request with
+// `handler` and remembers what it was sent, it stops when dropped
+pub struct MockServer {
+    pub base_url: String,
+    requests: Arc<Mutex<Vec<MockRequest>>>,
+    stopped: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl MockServer {
+    pub fn start(handler: impl Fn(&MockRequest) -> (u16, String) + Send + Sync + 'static) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(vec![]));
+        let stopped = Arc::new(AtomicBool::new(false));
+        let handler: Handler = Box::new(handler);
+        let thread = {
+            let requests = requests.clone();
+            let stopped = stopped.clone();
+            std::thread::spawn(move || {
+                for stream in listener.incoming() {
+                    if stopped.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    if let Ok(stream) = stream {
+                        serve(stream, &handler, &requests);
+                    }
+                }
+            })
+        };
+        MockServer { base_url, requests, stopped, thread: Some(thread) }
+    }
+
+    pub fn requests(&self) -> Vec<MockRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::SeqCst);
+        // wake the accept loop so it notices it was stopped
+        let _ = TcpStream::connect(self.base_url.trim_start_matches("http://"));
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn serve(stream: TcpStream, handler: &Handler, requests: &Mutex<Vec<MockRequest>>) {
+    let mut reader = BufReader::new(&stream);
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line).is_err() {
+        return;
+    }
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(path)) = (parts.next(), parts.next()) else {
+        return;
+    };
+    let mut headers = vec![];
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).is_err() || line.trim().is_empty() {
+            break;
+        }
+        if let Some((key, value)) = line.split_once(':') {
+            headers.push((key.trim().to_string(), value.trim().to_string()));
+        }
+    }
+    let mut request = MockRequest { method: method.to_string(), path: path.to_string(), headers, body: String::new() };
+    let length = request.header("content-length").and_then(|length| length.parse().ok()).unwrap_or(0);
+    let mut body = vec![0; length];
+    if reader.read_exact(&mut body).is_err() {
+        return;
+    }
+    request.body = String::from_utf8_lossy(&body).to_string();
+
+    let (status, body) = handler(&request);
+    requests.lock().unwrap().push(request);
+    let response = format!(
+        "HTTP/1.1 {status} MOCK\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len(),
+    );
+    let _ = (&stream).write_all(response.as_bytes());
+}
+
+fn page(message: &str) -> String {
+    format!("<!DOCTYPE html>\n<html><body><main>\n<article><p>{message}</p></article>\n</main></body></html>\n")
+}
+
+// a stand-in for the puzzle site that checks posted answers against
+// `answers` and replies with its usual wording
+pub fn fake_site(answers: Vec<(u32, Part, i64)>) -> MockServer {
+    let solved: Mutex<Vec<(u32, Part)>> = Mutex::new(vec![]);
+    MockServer::start(move |request| {
+        let day = request.path.strip_prefix("/2023/day/")
+            .and_then(|rest| rest.strip_suffix("/answer"))
+            .and_then(|day| day.parse::<u32>().ok());
+        let fields: Vec<(&str, &str)> = request.body.split('&').filter_map(|field| field.split_once('=')).collect();
+        let field = |name: &str| fields.iter().find(|(key, _)| *key == name).map(|(_, value)| *value);
+        let part = match field("level") {
+            Some("1") => Part::Easy,
+            Some("2") => Part::Hard,
+            _ => return (400, "Bad Request".to_string()),
+        };
+        let (Some(day), Some(answer)) = (day, field("answer")) else {
+            return (404, "404 Not Found".to_string());
+        };
+        let Some((_, _, expected)) = answers.iter().find(|(d, p, _)| (*d, *p) == (day, part)) else {
+            return (404, "404 Not Found".to_string());
+        };
+        let mut solved = solved.lock().unwrap();
+        if solved.contains(&(day, part)) {
+            return (200, page("You don't seem to be solving the right level.  Did you already complete it? [Return to Day]"));
+        }
+        let message = match answer.parse::<i64>() {
+            Ok(answer) if answer == *expected => {
+                solved.push((day, part));
+                "That's the right answer!  You are one gold star closer to restoring snow operations."
+            }
+            Ok(answer) if answer > *expected => "That's not the right answer; your answer is too high.  Please wait one minute before trying again.",
+            Ok(_) => "That's not the right answer; your answer is too low.  Please wait one minute before trying again.",
+            Err(_) => "That's not the right answer.  If you're stuck, make sure you're using the full input data.",
+        };
+        (200, page(message))
+    })
+}
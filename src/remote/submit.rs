@@ -0,0 +1,279 @@
+use std::{
+    fmt::Display,
+    io::Write,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    common::{error::AocError, parsing::Span},
+    registry::Part,
+};
+
+use super::http::Client;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Verdict {
+    Right,
+    Wrong,
+    TooHigh,
+    TooLow,
+    // nothing was checked, the previous submission was too recent
+    Wait(Duration),
+    AlreadySolved,
+    Unrecognized,
+}
+
+impl Verdict {
+    pub fn is_wrong(&self) -> bool {
+        matches!(self, Verdict::Wrong | Verdict::TooHigh | Verdict::TooLow)
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Right => write!(f, "right"),
+            Verdict::Wrong => write!(f, "wrong"),
+            Verdict::TooHigh => write!(f, "too-high"),
+            Verdict::TooLow => write!(f, "too-low"),
+            Verdict::Wait(wait) => write!(f, "wait-{}s", wait.as_secs()),
+            Verdict::AlreadySolved => write!(f, "already-solved"),
+            Verdict::Unrecognized => write!(f, "unrecognized"),
+        }
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "right" => Ok(Verdict::Right),
+            "wrong" => Ok(Verdict::Wrong),
+            "too-high" => Ok(Verdict::TooHigh),
+            "too-low" => Ok(Verdict::TooLow),
+            "already-solved" => Ok(Verdict::AlreadySolved),
+            "unrecognized" => Ok(Verdict::Unrecognized),
+            _ => s.strip_prefix("wait-")
+                .and_then(|wait| wait.strip_suffix('s'))
+                .and_then(|secs| secs.parse().ok())
+                .map(|secs| Verdict::Wait(Duration::from_secs(secs)))
+                .ok_or_else(|| format!("unknown verdict `{s}`")),
+        }
+    }
+}
+
+// `You have 1m 5s left to wait.`
+fn parse_wait(body: &str) -> Option<Duration> {
+    let start = body.find("You have ")? + "You have ".len();
+    let end = start + body[start..].find(" left to wait")?;
+    body[start..end].split_whitespace().try_fold(Duration::ZERO, |total, amount| {
+        let (number, unit) = amount.split_at(amount.find(|c: char| !c.is_ascii_digit())?);
+        let number: u64 = number.parse().ok()?;
+        match unit {
+            "h" => Some(total + Duration::from_secs(number * 3600)),
+            "m" => Some(total + Duration::from_secs(number * 60)),
+            "s" => Some(total + Duration::from_secs(number)),
+            _ => None,
+        }
+    })
+}
+
+// the site answers with an html page, the verdict is in the wording of its article
+pub fn parse_verdict(body: &str) -> Verdict {
+    if body.contains("That's the right answer") {
+        Verdict::Right
+    } else if body.contains("That's not the right answer") {
+        if body.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if body.contains("your answer is too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Wrong
+        }
+    } else if body.contains("You gave an answer too recently") {
+        Verdict::Wait(parse_wait(body).unwrap_or_default())
+    } else if body.contains("Did you already complete it") {
+        Verdict::AlreadySolved
+    } else {
+        Verdict::Unrecognized
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Attempt {
+    pub timestamp: u64,
+    pub day: u32,
+    pub part: Part,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+// every submission ever made, one `<unix time> <day> <part> <answer> <verdict>`
+// line each, appended as they happen
+pub struct AttemptLog {
+    path: PathBuf,
+    pub attempts: Vec<Attempt>,
+}
+
+impl AttemptLog {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, AocError> {
+        let path = path.as_ref().to_path_buf();
+        let s = match path.exists() {
+            true => std::fs::read_to_string(&path).map_err(|_| AocError::FileNotFound)?,
+            false => String::new(),
+        };
+        let mut attempts = vec![];
+        for line in Span::document(&s).lines() {
+            if line.is_blank() || line.text.starts_with('#') {
+                continue;
+            }
+            let words: Vec<Span> = line.words().collect();
+            let [timestamp, day, part, answer, verdict] = words.as_slice() else {
+                return Err(line.error("expected `<unix time> <day> <part> <answer> <verdict>`"));
+            };
+            attempts.push(Attempt {
+                timestamp: timestamp.parse("timestamp")?,
+                day: day.parse("day")?,
+                part: part.text.parse().map_err(|reason| part.error(reason))?,
+                answer: answer.text.to_string(),
+                verdict: verdict.text.parse().map_err(|reason| verdict.error(reason))?,
+            });
+        }
+        Ok(AttemptLog { path, attempts })
+    }
+
+    pub fn record(&mut self, attempt: Attempt) -> Result<(), AocError> {
+        let mut file = std::fs::OpenOptions::new().create(true).append(true).open(&self.path)
+            .map_err(|_| AocError::FileNotFound)?;
+        writeln!(file, "{} {} {} {} {}", attempt.timestamp, attempt.day, attempt.part, attempt.answer, attempt.verdict)
+            .map_err(|_| AocError::FileNotFound)?;
+        self.attempts.push(attempt);
+        Ok(())
+    }
+
+    // why `answer` is already known to be wrong, also using the too high and
+    // too low hints on numeric answers
+    pub fn known_wrong(&self, day: u32, part: Part, answer: &str) -> Option<String> {
+        let number = answer.parse::<i128>().ok();
+        self.attempts.iter()
+            .filter(|attempt| attempt.day == day && attempt.part == part && attempt.verdict.is_wrong())
+            .find_map(|attempt| {
+                let previous = attempt.answer.parse::<i128>().ok();
+                match (attempt.verdict, number, previous) {
+                    _ if attempt.answer == answer => Some(format!("{answer} was already rejected")),
+                    (Verdict::TooHigh, Some(n), Some(high)) if n >= high => Some(format!("{answer} is not below {high}, which was too high")),
+                    (Verdict::TooLow, Some(n), Some(low)) if n <= low => Some(format!("{answer} is not above {low}, which was too low")),
+                    _ => None,
+                }
+            })
+    }
+
+    pub fn solved(&self, day: u32, part: Part) -> Option<&str> {
+        self.attempts.iter()
+            .find(|attempt| attempt.day == day && attempt.part == part && attempt.verdict == Verdict::Right)
+            .map(|attempt| attempt.answer.as_str())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Submission {
+    Sent(Verdict),
+    // nothing was sent
+    Refused(String),
+}
+
+pub fn submit(client: &Client, log: &mut AttemptLog, day: u32, part: Part, answer: &str) -> Result<Submission, AocError> {
+    // answers are single words of the attempt log
+    if answer.is_empty() || answer.contains(char::is_whitespace) {
+        return Ok(Submission::Refused(format!("`{answer}` is not a single word")));
+    }
+    if let Some(solved) = log.solved(day, part) {
+        return Ok(Submission::Refused(format!("day {day} ({part}) was already solved with {solved}")));
+    }
+    if let Some(reason) = log.known_wrong(day, part, answer) {
+        return Ok(Submission::Refused(reason));
+    }
+    let level = match part {
+        Part::Easy => "1",
+        Part::Hard => "2",
+    };
+    let body = client.post_form(&format!("{}/answer", client.day_path(day)), &[("level", level), ("answer", answer)])?;
+    let verdict = parse_verdict(&body);
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+    log.record(Attempt { timestamp, day, part, answer: answer.to_string(), verdict })?;
+    Ok(Submission::Sent(verdict))
+}
+
+#[cfg(test)]
+mod test {
+    use rstest::rstest;
+
+    use crate::remote::{http::YEAR, mock::fake_site};
+
+    use super::*;
+
+    #[rstest]
+    #[case("<article><p>That's the right answer!  You are one gold star closer.</p></article>", Verdict::Right)]
+    #[case("<p>That's not the right answer.  If you're stuck, make sure you're using the full input data.</p>", Verdict::Wrong)]
+    #[case("<p>That's not the right answer; your answer is too high.</p>", Verdict::TooHigh)]
+    #[case("<p>That's not the right answer; your answer is too low.</p>", Verdict::TooLow)]
+    #[case("<p>You gave an answer too recently.  You have 34s left to wait.</p>", Verdict::Wait(Duration::from_secs(34)))]
+    #[case("<p>You gave an answer too recently.  You have 1m 5s left to wait.</p>", Verdict::Wait(Duration::from_secs(65)))]
+    #[case("<p>You don't seem to be solving the right level.  Did you already complete it?</p>", Verdict::AlreadySolved)]
+    #[case("<html>maintenance</html>", Verdict::Unrecognized)]
+    fn test_parse_verdict(#[case] body: &str, #[case] expected: Verdict) {
+        assert_eq!(expected, parse_verdict(body));
+        assert_eq!(Ok(expected), expected.to_string().parse());
+    }
+
+    #[test]
+    fn test_submit() {
+        let server = fake_site(vec![(1, Part::Easy, 142)]);
+        let client = Client::new(&server.base_url, YEAR, "abc", Duration::ZERO);
+        let path = std::env::temp_dir().join(format!("aoc_2023_attempts_{}.txt", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut log = AttemptLog::load(&path).unwrap();
+
+        for answer in ["", "1 2", "142\n"] {
+            let expected = Submission::Refused(format!("`{answer}` is not a single word"));
+            assert_eq!(Ok(expected), submit(&client, &mut log, 1, Part::Easy, answer));
+        }
+        assert_eq!(Ok(Submission::Sent(Verdict::TooHigh)), submit(&client, &mut log, 1, Part::Easy, "200"));
+        assert_eq!(
+            Ok(Submission::Refused("200 was already rejected".to_string())),
+            submit(&client, &mut log, 1, Part::Easy, "200"),
+        );
+        assert_eq!(
+            Ok(Submission::Refused("300 is not below 200, which was too high".to_string())),
+            submit(&client, &mut log, 1, Part::Easy, "300"),
+        );
+        assert_eq!(Ok(Submission::Sent(Verdict::Right)), submit(&client, &mut log, 1, Part::Easy, "142"));
+        assert_eq!(
+            Ok(Submission::Refused("day 1 (easy) was already solved with 142".to_string())),
+            submit(&client, &mut log, 1, Part::Easy, "141"),
+        );
+        assert_eq!(2, server.requests().len());
+        assert_eq!("level=1&answer=142", server.requests()[1].body);
+
+        let reloaded = AttemptLog::load(&path).unwrap();
+        assert_eq!(log.attempts, reloaded.attempts);
+        assert_eq!(Some("142"), reloaded.solved(1, Part::Easy));
+        std::fs::remove_file(&path).unwrap();
+
+        // a log that missed the right answer still gets told by the site
+        let mut stale = AttemptLog::load(&path).unwrap();
+        assert_eq!(Ok(Submission::Sent(Verdict::AlreadySolved)), submit(&client, &mut stale, 1, Part::Easy, "142"));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_load_errors() {
+        let path = std::env::temp_dir().join(format!("aoc_2023_bad_attempts_{}.txt", std::process::id()));
+        std::fs::write(&path, "# header\n1700000000 1 easy 142 maybe\n").unwrap();
+        assert_eq!(Err(AocError::at(2, 23, "unknown verdict `maybe`")), AttemptLog::load(&path).map(|log| log.attempts));
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,125 @@
+use std::{panic::{AssertUnwindSafe, catch_unwind}, time::{Duration, Instant}};
+
+use rayon::prelude::*;
+
+use crate::{common::{error::AocError, table::render}, registry::Solution};
+
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
+    Answer(String),
+    Failed(AocError),
+    Panicked(String),
+}
+
+#[derive(Debug)]
+pub struct RunResult {
+    pub solution: Solution,
+    pub outcome: Outcome,
+    pub elapsed: Duration,
+}
+
+impl RunResult {
+    pub fn succeeded(&self) -> bool {
+        matches!(self.outcome, Outcome::Answer(_))
+    }
+}
+
+fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+    payload.downcast_ref::<&str>().map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+// a panic is caught and reported like any other failure, the default hook
+// still prints it to stderr as it happens
+pub fn run_one(solution: Solution, input: &str) -> RunResult {
+    let start = Instant::now();
+    let outcome = match catch_unwind(AssertUnwindSafe(|| (solution.solve)(input))) {
+        Ok(Ok(answer)) => Outcome::Answer(answer),
+        Ok(Err(error)) => Outcome::Failed(error),
+        Err(payload) => Outcome::Panicked(panic_message(payload)),
+    };
+    RunResult { solution, outcome, elapsed: start.elapsed() }
+}
+
+// results come back in the order of `jobs`, whatever order they finish in
+pub fn run_parallel(jobs: Vec<(Solution, String)>, threads: Option<usize>) -> Result<Vec<RunResult>, AocError> {
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(threads.unwrap_or(0))
+        .build()
+        .map_err(|_| AocError::SolveError)?;
+    Ok(pool.install(|| {
+        jobs.into_par_iter()
+            .map(|(solution, input)| run_one(solution, &input))
+            .collect()
+    }))
+}
+
+pub fn describe(outcome: &Outcome) -> (&str, String) {
+    match outcome {
+        Outcome::Answer(answer) => (answer, String::new()),
+        Outcome::Failed(error) => ("-", format!("{error:?}")),
+        Outcome::Panicked(message) => ("-", format!("panicked: {message}")),
+    }
+}
+
+pub fn format_table(results: &[RunResult]) -> String {
+    let header = ["day", "part", "variant", "answer", "time", "error"].map(String::from).to_vec();
+    let rows = results.iter()
+        .map(|result| {
+            let (answer, error) = describe(&result.outcome);
+            vec![
+                result.solution.day.to_string(),
+                result.solution.part.to_string(),
+                result.solution.variant.to_string(),
+                answer.to_string(),
+                format!("{:.3}ms", result.elapsed.as_secs_f64() * 1000.0),
+                error,
+            ]
+        });
+    let mut table = render(&std::iter::once(header).chain(rows).collect::<Vec<_>>());
+    let failed = results.iter().filter(|result| !result.succeeded()).count();
+    let total: Duration = results.iter().map(|result| result.elapsed).sum();
+    table.push_str(&format!("{} solutions, {} failed, {:.3}ms total\n", results.len(), failed, total.as_secs_f64() * 1000.0));
+    table
+}
+
+#[cfg(test)]
+mod test {
+    use crate::registry::{Part, select, IDIOMATIC};
+
+    use super::*;
+
+    fn failing(_: &str) -> Result<String, AocError> {
+        Err(AocError::SolveError)
+    }
+
+    fn panicking(_: &str) -> Result<String, AocError> {
+        panic!("boom")
+    }
+
+    #[test]
+    fn test_failures_are_isolated() {
+        let mut jobs: Vec<(Solution, String)> = select(IDIOMATIC).into_iter()
+            .filter(Solution::has_puzzle_input)
+            .map(|solution| (solution, solution.puzzle_input()))
+            .collect();
+        let solved = jobs.len();
+        let failing = Solution { day: 7, part: Part::Easy, variant: "failing", solve: failing };
+        let panicking = Solution { day: 7, part: Part::Hard, variant: "panicking", solve: panicking };
+        jobs.push((failing, String::new()));
+        jobs.push((panicking, String::new()));
+
+        let results = run_parallel(jobs, Some(4)).unwrap();
+        assert_eq!(solved + 2, results.len());
+        assert!(results[..solved].iter().all(RunResult::succeeded));
+        assert_eq!(Outcome::Answer("55108".to_string()), results[0].outcome);
+        assert_eq!(Outcome::Failed(AocError::SolveError), results[solved].outcome);
+        assert_eq!(Outcome::Panicked("boom".to_string()), results[solved + 1].outcome);
+
+        let table = format_table(&results);
+        assert!(table.starts_with("day  part  variant    answer"));
+        assert!(table.contains("panicked: boom"));
+        assert!(table.contains(&format!("{} solutions, 2 failed", solved + 2)));
+    }
+}
@@ -0,0 +1,204 @@
+use std::path::{Path, PathBuf};
+
+use crate::common::error::AocError;
+
+// `{module}` is replaced with `day_XX` and `{part}` with `easy` or `hard`
+const COMMON: &str = r#"use crate::common::error::AocError;
+
+pub fn parse(filename: &str) -> Result<Vec<String>, AocError> {
+    let s = std::fs::read_to_string(filename).map_err(|_| AocError::FileNotFound)?;
+    Ok(s.lines().map(String::from).collect())
+}
+"#;
+
+const PART: &str = r#"use crate::{common::error::AocError, {module}::common::parse};
+
+pub fn solve_file(filename: &str) -> Result<i64, AocError> {
+    let _lines = parse(filename)?;
+    Err(AocError::SolveError)
+}
+
+#[allow(unused)]
+pub fn solve() -> Result<i64, AocError> {
+    solve_file("input/{module}/puzzle.txt")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    #[ignore = "not solved yet"]
+    fn test_testcase() {
+        assert_eq!(Ok(0), solve_file("input/{module}/{part}_test.txt"));
+    }
+
+    #[test]
+    #[ignore = "not solved yet"]
+    fn test_solve() {
+        assert_eq!(Ok(0), solve())
+    }
+}
+"#;
+
+fn module(day: u32) -> String {
+    format!("day_{day:02}")
+}
+
+fn scaffold_error(reason: impl Into<String>) -> AocError {
+    AocError::Scaffold { reason: reason.into() }
+}
+
+// a `mod day_XX` block after the last day's block
+pub fn register_module(main_rs: &str, day: u32) -> Result<String, AocError> {
+    let module = module(day);
+    if main_rs.contains(&format!("mod {module} {{")) {
+        return Err(scaffold_error(format!("{module} is already declared")));
+    }
+    let last = main_rs.rfind("\nmod day_").ok_or_else(|| scaffold_error("no day modules in main.rs"))?;
+    let end = last + main_rs[last..].find("\n}\n").ok_or_else(|| scaffold_error("unterminated day module"))? + "\n}\n".len();
+    let block = format!("\nmod {module} {{\n    pub mod common;\n    pub mod easy;\n    pub mod hard;\n}}\n");
+    Ok(format!("{}{}{}", &main_rs[..end], block, &main_rs[end..]))
+}
+
+// imports the day and registers its idiomatic parts at the end of `solutions`
+pub fn register_solutions(registry_rs: &str, day: u32) -> Result<String, AocError> {
+    let module = module(day);
+    let import = registry_rs.find("use crate::{day_").ok_or_else(|| scaffold_error("no day imports in registry.rs"))?;
+    let import_end = import + registry_rs[import..].find("};").ok_or_else(|| scaffold_error("unterminated day imports"))?;
+    let solutions = registry_rs.find("pub fn solutions()").ok_or_else(|| scaffold_error("no `solutions` in registry.rs"))?;
+    let list_end = solutions + registry_rs[solutions..].find("\n    ]\n}").ok_or_else(|| scaffold_error("unterminated `solutions`"))?;
+    let entries = format!(
+        "\n\n        solution({day}, Easy, IDIOMATIC, |f| display({module}::easy::solve_file(f))),\
+         \n        solution({day}, Hard, IDIOMATIC, |f| display({module}::hard::solve_file(f))),",
+    );
+    Ok(format!(
+        "{}, {}{}{}{}",
+        &registry_rs[..import_end], module, &registry_rs[import_end..list_end], entries, &registry_rs[list_end..],
+    ))
+}
+
+// writes the day's sources and empty inputs, and registers the day, `root`
+// being the crate directory. the empty puzzle input is skipped by `run` and
+// replaced by `fetch`
+pub fn new_day(root: &Path, day: u32) -> Result<Vec<PathBuf>, AocError> {
+    let module = module(day);
+    let src = root.join("src").join(&module);
+    if src.exists() {
+        return Err(scaffold_error(format!("{} already exists", src.display())));
+    }
+    let read = |path: &Path| std::fs::read_to_string(path).map_err(|_| AocError::FileNotFound);
+    let write = |path: &Path, contents: &str| std::fs::write(path, contents).map_err(|_| AocError::FileNotFound);
+    let main_rs = root.join("src/main.rs");
+    let registry_rs = root.join("src/registry.rs");
+    // both edits are checked before anything is written
+    let main_source = register_module(&read(&main_rs)?, day)?;
+    let registry_source = register_solutions(&read(&registry_rs)?, day)?;
+
+    let input = root.join("input").join(&module);
+    std::fs::create_dir_all(&src).map_err(|_| AocError::FileNotFound)?;
+    std::fs::create_dir_all(&input).map_err(|_| AocError::FileNotFound)?;
+    let part = |part: &str| PART.replace("{module}", &module).replace("{part}", part);
+    let mut created = vec![];
+    for (path, contents) in [
+        (src.join("common.rs"), COMMON.to_string()),
+        (src.join("easy.rs"), part("easy")),
+        (src.join("hard.rs"), part("hard")),
+        (input.join("easy_test.txt"), String::new()),
+        (input.join("hard_test.txt"), String::new()),
+        (input.join("puzzle.txt"), String::new()),
+    ] {
+        if !path.exists() {
+            write(&path, &contents)?;
+            created.push(path);
+        }
+    }
+    write(&main_rs, &main_source)?;
+    write(&registry_rs, &registry_source)?;
+    Ok(created)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const MAIN_RS: &str = "\
+mod common {
+    pub mod error;
+}
+
+mod day_01 {
+    pub mod easy;
+    pub mod hard;
+}
+
+mod cli;
+
+fn main() {}
+";
+
+    const REGISTRY_RS: &str = "\
+use crate::{day_01};
+
+pub fn solutions() -> Vec<Solution> {
+    use Part::{Easy, Hard};
+    vec![
+        solution(1, Easy, IDIOMATIC, |f| display(day_01::easy::sum_file(f))),
+        solution(1, Hard, IDIOMATIC, |f| display(day_01::hard::sum_file(f))),
+    ]
+}
+";
+
+    #[test]
+    fn test_register_module() {
+        let registered = register_module(MAIN_RS, 2).unwrap();
+        let expected = MAIN_RS.replace(
+            "    pub mod hard;\n}\n",
+            "    pub mod hard;\n}\n\nmod day_02 {\n    pub mod common;\n    pub mod easy;\n    pub mod hard;\n}\n",
+        );
+        assert_eq!(expected, registered);
+        assert_eq!(
+            Err(AocError::Scaffold { reason: "day_01 is already declared".to_string() }),
+            register_module(MAIN_RS, 1),
+        );
+        assert!(matches!(register_module("fn main() {}\n", 2), Err(AocError::Scaffold { .. })));
+    }
+
+    #[test]
+    fn test_register_solutions() {
+        let registered = register_solutions(REGISTRY_RS, 2).unwrap();
+        let expected = REGISTRY_RS
+            .replace("{day_01}", "{day_01, day_02}")
+            .replace("sum_file(f))),\n    ]", concat!(
+                "sum_file(f))),\n\n",
+                "        solution(2, Easy, IDIOMATIC, |f| display(day_02::easy::solve_file(f))),\n",
+                "        solution(2, Hard, IDIOMATIC, |f| display(day_02::hard::solve_file(f))),\n",
+                "    ]",
+            ));
+        assert_eq!(expected, registered);
+    }
+
+    #[test]
+    fn test_new_day() {
+        let root = std::env::temp_dir().join(format!("aoc_2023_scaffold_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(root.join("src/main.rs"), MAIN_RS).unwrap();
+        std::fs::write(root.join("src/registry.rs"), REGISTRY_RS).unwrap();
+
+        let created = new_day(&root, 12).unwrap();
+        assert_eq!(6, created.len());
+        let easy = std::fs::read_to_string(root.join("src/day_12/easy.rs")).unwrap();
+        assert!(easy.starts_with("use crate::{common::error::AocError, day_12::common::parse};"));
+        assert!(easy.contains("solve_file(\"input/day_12/easy_test.txt\")"));
+        let hard = std::fs::read_to_string(root.join("src/day_12/hard.rs")).unwrap();
+        assert!(hard.contains("solve_file(\"input/day_12/hard_test.txt\")"));
+        assert!(root.join("input/day_12/hard_test.txt").exists());
+        assert!(root.join("input/day_12/easy_test.txt").exists());
+        assert_eq!("", std::fs::read_to_string(root.join("input/day_12/puzzle.txt")).unwrap());
+        assert!(std::fs::read_to_string(root.join("src/main.rs")).unwrap().contains("mod day_12 {"));
+
+        assert!(matches!(new_day(&root, 12), Err(AocError::Scaffold { .. })));
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
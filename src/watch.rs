@@ -0,0 +1,185 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, hash_map::DefaultHasher},
+    hash::{Hash, Hasher},
+};
+
+use crate::{
+    batch::{BatchResult, InputFile, Verdict, discover, load_answers, run_batch},
+    common::error::AocError,
+    registry::{Part, Solution},
+    runner::Outcome,
+};
+
+// what changed is told apart by content rather than modification time, so
+// two quick saves within the timestamp resolution are still both seen
+fn fingerprint(path: &str) -> Option<u64> {
+    let contents = std::fs::read(path).ok()?;
+    let mut hasher = DefaultHasher::new();
+    contents.hash(&mut hasher);
+    Some(hasher.finish())
+}
+
+fn answer_text(outcome: &Outcome) -> String {
+    match outcome {
+        Outcome::Answer(answer) => answer.clone(),
+        Outcome::Failed(error) => format!("error {error:?}"),
+        Outcome::Panicked(message) => format!("panicked: {message}"),
+    }
+}
+
+pub enum Change {
+    Solved { result: BatchResult, previous: Option<String> },
+    Removed(InputFile),
+}
+
+// re-solves a day's example inputs, and its puzzle input when asked to,
+// whenever one of them is added or edited. only the inputs are watched, the
+// solutions themselves are compiled in
+pub struct Watcher {
+    day: u32,
+    dir: String,
+    answers: String,
+    solutions: Vec<Solution>,
+    include_puzzle: bool,
+    seen: BTreeMap<String, (InputFile, u64)>,
+    previous: BTreeMap<(Part, String), String>,
+}
+
+impl Watcher {
+    pub fn new(day: u32, dir: &str, answers: &str, solutions: Vec<Solution>, include_puzzle: bool) -> Self {
+        let solutions = solutions.into_iter().filter(|solution| solution.day == day).collect();
+        Watcher {
+            day,
+            dir: dir.to_string(),
+            answers: answers.to_string(),
+            solutions,
+            include_puzzle,
+            seen: BTreeMap::new(),
+            previous: BTreeMap::new(),
+        }
+    }
+
+    // the inputs added or edited since the last poll, and the ones gone since
+    fn changed_inputs(&mut self) -> Result<(Vec<InputFile>, Vec<InputFile>), AocError> {
+        let mut changed = vec![];
+        let mut present = BTreeSet::new();
+        for input in discover(&self.dir)? {
+            if input.day != self.day || !(input.name.ends_with("_test") || self.include_puzzle && input.name == "puzzle") {
+                continue;
+            }
+            let Some(fingerprint) = fingerprint(&input.path) else {
+                continue;
+            };
+            present.insert(input.path.clone());
+            let previous = self.seen.insert(input.path.clone(), (input.clone(), fingerprint));
+            if previous.map(|(_, previous)| previous) != Some(fingerprint) {
+                changed.push(input);
+            }
+        }
+        let gone: Vec<String> = self.seen.keys().filter(|path| !present.contains(*path)).cloned().collect();
+        let removed = gone.iter().filter_map(|path| self.seen.remove(path)).map(|(input, _)| input).collect();
+        Ok((changed, removed))
+    }
+
+    // the first poll solves every watched input
+    pub fn poll(&mut self) -> Result<Vec<Change>, AocError> {
+        let (changed, removed) = self.changed_inputs()?;
+        let mut changes = vec![];
+        if !changed.is_empty() {
+            // reloaded each time, as expected answers tend to be written alongside the example
+            let results = load_answers(&self.answers)
+                .and_then(|answers| run_batch(&changed, &self.solutions, &answers, Some(1)));
+            // inputs that could not be solved count as changed again on the next poll
+            let results = results.inspect_err(|_| {
+                for input in &changed {
+                    self.seen.remove(&input.path);
+                }
+            })?;
+            for result in results {
+                let key = (result.result.solution.part, result.input.name.clone());
+                let previous = self.previous.insert(key, answer_text(&result.result.outcome));
+                changes.push(Change::Solved { result, previous });
+            }
+        }
+        for input in removed {
+            self.previous.retain(|(_, name), _| *name != input.name);
+            changes.push(Change::Removed(input));
+        }
+        Ok(changes)
+    }
+}
+
+pub fn format_change(change: &Change) -> String {
+    let (result, previous) = match change {
+        Change::Solved { result, previous } => (result, previous),
+        Change::Removed(input) => return format!("{}: removed", input.name),
+    };
+    let answer = answer_text(&result.result.outcome);
+    let mut line = format!("{} ({}): {}", result.input.name, result.result.solution.part, answer);
+    match previous {
+        Some(previous) if *previous == answer => line.push_str(", unchanged"),
+        Some(previous) => line.push_str(&format!(", was {previous}")),
+        None => {}
+    }
+    if let Some(expected) = &result.expected {
+        let verdict = match result.verdict() {
+            Verdict::Correct => "ok",
+            _ => "WRONG",
+        };
+        line.push_str(&format!(", expected {expected} {verdict}"));
+    }
+    line
+}
+
+#[cfg(test)]
+mod test {
+    use crate::registry::{IDIOMATIC, select};
+
+    use super::*;
+
+    #[test]
+    fn test_watch() {
+        let dir = std::env::temp_dir().join(format!("aoc_2023_watch_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("day_01")).unwrap();
+        let example = dir.join("day_01/easy_test.txt");
+        std::fs::copy("input/day_01/easy_test.txt", &example).unwrap();
+        std::fs::write(dir.join("day_01/puzzle.txt"), "12\n").unwrap();
+        let mut watcher = Watcher::new(1, dir.to_str().unwrap(), "input/answers.txt", select(IDIOMATIC), false);
+
+        let lines = |changes: Vec<Change>| changes.iter().map(format_change).collect::<Vec<_>>();
+        assert_eq!(
+            vec!["easy_test (easy): 142, expected 142 ok", "easy_test (hard): 142"],
+            lines(watcher.poll().unwrap()),
+        );
+        assert!(watcher.poll().unwrap().is_empty());
+
+        std::fs::write(&example, "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet\nseven7\n").unwrap();
+        assert_eq!(
+            vec!["easy_test (easy): 219, was 142, expected 142 WRONG", "easy_test (hard): 219, was 142"],
+            lines(watcher.poll().unwrap()),
+        );
+
+        std::fs::write(&example, "1abc2\nnothing\n").unwrap();
+        assert_eq!(
+            "easy_test (easy): error Located { line: 2, column: 1, reason: \"no calibration digits\" }, was 219, expected 142 WRONG",
+            format_change(&watcher.poll().unwrap()[0]),
+        );
+
+        std::fs::write(dir.join("answers.txt"), "not an answer\n").unwrap();
+        let mut broken = Watcher::new(1, dir.to_str().unwrap(), dir.join("answers.txt").to_str().unwrap(), select(IDIOMATIC), false);
+        assert!(broken.poll().is_err());
+        std::fs::write(dir.join("answers.txt"), "").unwrap();
+        assert_eq!(2, broken.poll().unwrap().len());
+
+        std::fs::remove_file(&example).unwrap();
+        assert_eq!(vec!["easy_test: removed"], lines(watcher.poll().unwrap()));
+        assert!(watcher.poll().unwrap().is_empty());
+        std::fs::copy("input/day_01/easy_test.txt", &example).unwrap();
+        assert_eq!(
+            vec!["easy_test (easy): 142, expected 142 ok", "easy_test (hard): 142"],
+            lines(watcher.poll().unwrap()),
+        );
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}